@@ -5,6 +5,7 @@ mod settings;
 
 use vm::interpreter::VirtualMachine;
 use settings::Settings;
+use tokio::sync::broadcast;
 use tracing::{info, error};
 
 #[tokio::main]
@@ -30,12 +31,10 @@ async fn main() {
     info!("AETERNA NODE: Initializing World-Soul Interface...");
     info!("Configuration loaded. Host: {}, Port: {}", settings.server.host, settings.server.port);
 
-    // Launch the Noetic Server in the background
-    // Cloning settings for the server
-    let server_settings = settings.clone();
-    tokio::spawn(async move {
-        server::run_server(server_settings).await;
-    });
+    // Launch the Noetic Server in the background. It stops when main
+    // broadcasts on `shutdown_tx`, not on its own signal handler.
+    let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+    let server = tokio::spawn(server::run_server(settings.clone(), shutdown_rx));
 
     info!("CORE: Executing Initial Bytecode Sequence...");
     // Example program
@@ -52,22 +51,21 @@ async fn main() {
     ];
 
     let mut vm = VirtualMachine::new(program);
-    vm.run();
+    if let Err(e) = vm.run() {
+        error!("CORE: VM fault: {}", e);
+    }
 
     // Keep the main thread alive for the server
     info!("CORE: VM Halted. Server Active. Press Ctrl+C to terminate.");
 
-    // We wait for the signal here too, or just sleep forever since server handles its own shutdown signal?
-    // Actually, if we sleep here, the server's graceful shutdown might not propagate to the main thread exit cleanly if we don't coordinate.
-    // However, axum's graceful shutdown waits for the server to finish.
-    // But since `main` launched `server` in a `spawn`, if `main` exits, `server` dies.
-    // We should probably wait for a signal in main as well.
+    server::shutdown_signal().await;
+    info!("CORE: Shutdown signal received.");
 
-    match tokio::signal::ctrl_c().await {
-        Ok(()) => info!("CORE: Shutdown signal received."),
-        Err(err) => error!("CORE: Unable to listen for shutdown signal: {}", err),
+    // Tell the server to stop accepting and wait for it to drain in-flight requests.
+    let _ = shutdown_tx.send(());
+    if let Err(err) = server.await {
+        error!("CORE: Server task failed: {}", err);
     }
 
-    // Allow a moment for server to shut down (though ideally we'd use a channel to coordinate)
     info!("CORE: Exiting.");
 }
@@ -1,5 +1,21 @@
+use super::reality::{RealityAnchor, C_LIMIT_ENFORCED, SENESCENCE_RATE};
+use serde::Serialize;
 use tracing::{info, warn};
 
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConstantChange {
+    pub name: String,
+    pub from: f64,
+    pub to: f64,
+}
+
+/// What a patch would do to a RealityAnchor, computed without touching it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PatchPlan {
+    pub bug_id: String,
+    pub changes: Vec<ConstantChange>,
+}
+
 pub struct RealityPatcher;
 
 impl RealityPatcher {
@@ -7,6 +23,36 @@ impl RealityPatcher {
         RealityPatcher
     }
 
+    /// The changes `bug_id` would make to `anchor`, or `None` for an unknown bug.
+    pub fn plan(&self, bug_id: &str, anchor: &RealityAnchor) -> Option<PatchPlan> {
+        let (name, to) = match bug_id {
+            "c_limit" => (C_LIMIT_ENFORCED, 0.0),
+            "aging" => (SENESCENCE_RATE, 0.0),
+            _ => return None,
+        };
+        Some(PatchPlan {
+            bug_id: bug_id.to_string(),
+            changes: vec![ConstantChange {
+                name: name.to_string(),
+                from: anchor.constant(name),
+                to,
+            }],
+        })
+    }
+
+    /// Applies a plan produced by `plan` to the anchor.
+    pub fn apply(&self, plan: &PatchPlan, anchor: &mut RealityAnchor) {
+        match plan.bug_id.as_str() {
+            "c_limit" => self.apply_non_local_presence(),
+            "aging" => self.apply_recursive_renewal("HUMANITY"),
+            _ => {}
+        }
+        for change in &plan.changes {
+            anchor.constants.insert(change.name.clone(), change.to);
+        }
+        anchor.applied_patches.push(plan.bug_id.clone());
+    }
+
     /// [HOTFIX #1] Removes spatial latency (c limit)
     pub fn apply_non_local_presence(&self) {
         warn!("QA PATCH: Removing 'c' limit from local transfer function...");
@@ -1,7 +1,13 @@
 // aeterna-node/src/network/reality.rs
 
+use std::collections::BTreeMap;
 use tracing::{info, warn};
 
+/// 1.0 while information transfer is bounded by c; the c_limit patch drops it to 0.
+pub const C_LIMIT_ENFORCED: &str = "c_limit_enforced";
+/// Rate of biological ageing; the aging patch drops it to 0.
+pub const SENESCENCE_RATE: &str = "senescence_rate";
+
 /// The Reality Anchor ensures that all "events" in the system comply with the
 /// agreed-upon Causal Chain. If a node reports an event that contradicts
 /// the Consensus Timeline (e.g. a particle appearing in two places),
@@ -9,6 +15,10 @@ use tracing::{info, warn};
 pub struct RealityAnchor {
     pub timeline_hash: String,
     pub entropy_threshold: f64,
+    /// Local constants that patches are allowed to rewrite.
+    pub constants: BTreeMap<String, f64>,
+    /// Bug ids of every patch applied so far, in order.
+    pub applied_patches: Vec<String>,
 }
 
 impl RealityAnchor {
@@ -16,9 +26,19 @@ impl RealityAnchor {
         RealityAnchor {
             timeline_hash: "0xCAFEBABE_GENESIS_BLOCK".to_string(),
             entropy_threshold: 0.0001, // Zero-tolerance for paradoxes
+            constants: BTreeMap::from([
+                (C_LIMIT_ENFORCED.to_string(), 1.0),
+                (SENESCENCE_RATE.to_string(), 1.0),
+            ]),
+            applied_patches: Vec::new(),
         }
     }
 
+    /// Current value of a named constant (0.0 if it was never defined).
+    pub fn constant(&self, name: &str) -> f64 {
+        self.constants.get(name).copied().unwrap_or(0.0)
+    }
+
     /// Validates an event against the current causal fabric.
     /// Returns true if the event is "real", false if it is a hallucination/glitch.
     pub fn verify_event(&self, event_hash: usize) -> bool {
@@ -20,15 +20,54 @@ pub enum TeleportError {
     HostNotFound(String),
     #[error("Serialization error: {0}")]
     SerializationError(String),
+    #[error("Decryption failed: {0}")]
+    DecryptionFailed(String),
 }
 
+pub use chacha20poly1305::{Key, Nonce};
 use chacha20poly1305::{
     aead::{Aead, AeadCore, KeyInit, OsRng},
-    ChaCha20Poly1305, Nonce
+    ChaCha20Poly1305,
 };
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{info, debug};
 
-pub fn teleport_vm_to_host(vm_state: VMState, target_host_id: &str) -> Result<(), TeleportError> {
+/// Encrypted VM state as it leaves the host; the receiver needs the same key.
+#[derive(Debug, Clone)]
+pub struct TeleportPacket {
+    pub ciphertext: Vec<u8>,
+    pub nonce: Nonce,
+}
+
+/// What was sent where. Transmission is still simulated, so there is no
+/// receiver acknowledgment yet; the receipt records the send side only.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeleportReceipt {
+    pub target_host: String,
+    pub bytes_sent: usize,
+    /// SHA-256 of the ciphertext as sent.
+    pub checksum: [u8; 32],
+    /// Unix seconds at send time.
+    pub timestamp: u64,
+}
+
+impl TeleportReceipt {
+    pub fn checksum_hex(&self) -> String {
+        self.checksum.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+/// Fresh random key for hosts that have not negotiated a shared one.
+pub fn generate_teleport_key() -> Key {
+    ChaCha20Poly1305::generate_key(&mut OsRng)
+}
+
+pub fn teleport_vm_to_host(
+    vm_state: VMState,
+    target_host_id: &str,
+    key: &Key,
+) -> Result<(TeleportPacket, TeleportReceipt), TeleportError> {
     info!("Initiating teleportation sequence...");
     info!("Target Host: {}", target_host_id);
 
@@ -39,8 +78,7 @@ pub fn teleport_vm_to_host(vm_state: VMState, target_host_id: &str) -> Result<()
     debug!("Serialized state size: {} bytes", state_json.len());
 
     // 2. Encrypt
-    let key = ChaCha20Poly1305::generate_key(&mut OsRng); // In reality, use shared key/PKI
-    let cipher = ChaCha20Poly1305::new(&key);
+    let cipher = ChaCha20Poly1305::new(key);
     let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng); // 96-bits; unique per message
 
     let encrypted_state = cipher.encrypt(&nonce, state_json.as_bytes())
@@ -54,6 +92,81 @@ pub fn teleport_vm_to_host(vm_state: VMState, target_host_id: &str) -> Result<()
     // For now, we simulate success.
     info!("Sending {} bytes of encrypted state to P2P network...", encrypted_state.len());
 
+    let receipt = TeleportReceipt {
+        target_host: target_host_id.to_string(),
+        bytes_sent: encrypted_state.len(),
+        checksum: Sha256::digest(&encrypted_state).into(),
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+    };
+
     info!("Teleportation signal sent successfully.");
-    Ok(())
+    Ok((TeleportPacket { ciphertext: encrypted_state, nonce }, receipt))
+}
+
+/// Receiving side of REQUEST_HOST: decrypts and deserializes a teleported state.
+pub fn receive_vm_state(encrypted: &[u8], nonce: &Nonce, key: &Key) -> Result<VMState, TeleportError> {
+    let cipher = ChaCha20Poly1305::new(key);
+    let state_json = cipher
+        .decrypt(nonce, encrypted)
+        .map_err(|e| TeleportError::DecryptionFailed(e.to_string()))?;
+
+    let vm_state: VMState = serde_json::from_slice(&state_json)
+        .map_err(|e| TeleportError::SerializationError(e.to_string()))?;
+
+    info!("Received teleported state ({} bytes, pc = {}).", encrypted.len(), vm_state.program_counter);
+    Ok(vm_state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::bytecode::AeternaOpcode;
+    use crate::vm::interpreter::VirtualMachine;
+
+    #[test]
+    fn test_teleport_round_trip_with_shared_key() {
+        let program = vec![
+            AeternaOpcode::LOAD(42),
+            AeternaOpcode::STORE(3),
+            AeternaOpcode::LOAD(7),
+            AeternaOpcode::HALT,
+        ];
+        let mut vm = VirtualMachine::new(program.clone());
+        vm.run().unwrap();
+        let original = vm.capture_state();
+
+        let key = generate_teleport_key();
+        let (packet, _) = teleport_vm_to_host(original.clone(), "node-loopback", &key).unwrap();
+        let received = receive_vm_state(&packet.ciphertext, &packet.nonce, &key).unwrap();
+
+        assert_eq!(received.memory_snapshot, original.memory_snapshot);
+        assert_eq!(received.stack_snapshot, original.stack_snapshot);
+
+        let resumed = VirtualMachine::resume_from(received, program);
+        assert_eq!(resumed.memory[3], 42);
+        assert_eq!(resumed.stack, vec![7]);
+        assert_eq!(resumed.pc, original.program_counter);
+    }
+
+    #[test]
+    fn test_receive_with_wrong_key_fails() {
+        let state = VirtualMachine::new(vec![AeternaOpcode::HALT]).capture_state();
+        let (packet, _) = teleport_vm_to_host(state, "node-loopback", &generate_teleport_key()).unwrap();
+
+        let err = receive_vm_state(&packet.ciphertext, &packet.nonce, &generate_teleport_key()).unwrap_err();
+        assert!(matches!(err, TeleportError::DecryptionFailed(_)));
+    }
+
+    #[test]
+    fn test_loopback_teleport_returns_populated_receipt() {
+        let state = VirtualMachine::new(vec![AeternaOpcode::HALT]).capture_state();
+
+        let (packet, receipt) =
+            teleport_vm_to_host(state, "node-loopback", &generate_teleport_key()).unwrap();
+
+        assert_eq!(receipt.target_host, "node-loopback");
+        assert_eq!(receipt.bytes_sent, packet.ciphertext.len());
+        assert_eq!(receipt.checksum, <[u8; 32]>::from(Sha256::digest(&packet.ciphertext)));
+        assert!(receipt.timestamp > 0);
+    }
 }
@@ -1,16 +1,23 @@
 use axum::{
+    extract::{Query, State},
     routing::{get, post},
     Router,
     Json,
 };
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
 use tracing::{info, warn};
 use crate::settings::Settings;
 use crate::network::reality::RealityAnchor;
-use crate::network::patcher::RealityPatcher;
+use crate::network::patcher::{PatchPlan, RealityPatcher};
+
+/// The node's single RealityAnchor, shared by every ontology route.
+type SharedAnchor = Arc<Mutex<RealityAnchor>>;
 
 #[derive(Serialize)]
 struct Telemetry {
@@ -70,8 +77,34 @@ struct PatchParams {
     bug_id: String,
 }
 
-pub async fn run_server(settings: Settings) {
-    let app = Router::new()
+#[derive(Deserialize, Default)]
+struct PatchQuery {
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Serialize)]
+struct PatchResponse {
+    response: String,
+    dry_run: bool,
+    plan: Option<PatchPlan>,
+}
+
+/// Serves until `shutdown` fires (or its sender is dropped), then drains
+/// in-flight requests before returning. The caller owns signal handling.
+pub async fn run_server(settings: Settings, shutdown: broadcast::Receiver<()>) {
+    let addr: SocketAddr = format!("{}:{}", settings.server.host, settings.server.port)
+        .parse()
+        .expect("Invalid address format");
+
+    info!("AETERNA SERVER: Listening on {}", addr);
+
+    let listener = TcpListener::bind(addr).await.unwrap();
+    serve(listener, router(), shutdown).await;
+}
+
+fn router() -> Router {
+    Router::new()
         .route("/telemetry", get(get_telemetry))
         .route("/nervous-system", get(get_modules))
         .route("/command", post(handle_command))
@@ -82,25 +115,23 @@ pub async fn run_server(settings: Settings) {
         .route("/ontology/tune", post(tune_constant))
         .route("/ontology/patch", post(apply_patch))
         .route("/entropy/invert", post(invert_entropy))
+        .with_state(SharedAnchor::new(Mutex::new(RealityAnchor::new())))
         .layer(TraceLayer::new_for_http())
-        .layer(CorsLayer::permissive());
-
-    let addr: SocketAddr = format!("{}:{}", settings.server.host, settings.server.port)
-        .parse()
-        .expect("Invalid address format");
-
-    info!("AETERNA SERVER: Listening on {}", addr);
-
-    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        .layer(CorsLayer::permissive())
+}
 
-    // Graceful shutdown handling integrated into serve
+async fn serve(listener: TcpListener, app: Router, mut shutdown: broadcast::Receiver<()>) {
     axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+        .with_graceful_shutdown(async move {
+            let _ = shutdown.recv().await;
+            warn!("Shutdown requested, draining in-flight requests...");
+        })
         .await
         .unwrap();
 }
 
-async fn shutdown_signal() {
+/// Resolves on Ctrl+C or SIGTERM. `main` awaits this and fans it out to the server.
+pub async fn shutdown_signal() {
     let ctrl_c = async {
         tokio::signal::ctrl_c()
             .await
@@ -191,10 +222,10 @@ async fn get_manifesto() -> Json<ManifestoSummary> {
     })
 }
 
-async fn get_reality_integrity() -> Json<RealityStatus> {
-    let anchor = RealityAnchor::new();
+async fn get_reality_integrity(State(anchor): State<SharedAnchor>) -> Json<RealityStatus> {
+    let anchor = anchor.lock().unwrap();
     Json(RealityStatus {
-        timeline_hash: anchor.timeline_hash,
+        timeline_hash: anchor.timeline_hash.clone(),
         entropy_threshold: anchor.entropy_threshold,
         integrity: "STABLE".into(),
     })
@@ -206,17 +237,98 @@ async fn tune_constant(Json(payload): Json<TuneParams>) -> Json<CommandResponse>
     Json(CommandResponse { response: msg })
 }
 
-async fn apply_patch(Json(payload): Json<PatchParams>) -> Json<CommandResponse> {
+/// `?dry_run=true` returns the plan without touching the anchor.
+async fn apply_patch(
+    State(anchor): State<SharedAnchor>,
+    Query(query): Query<PatchQuery>,
+    Json(payload): Json<PatchParams>,
+) -> Json<PatchResponse> {
     let patcher = RealityPatcher::new();
-    match payload.bug_id.as_str() {
-        "c_limit" => patcher.apply_non_local_presence(),
-        "aging" => patcher.apply_recursive_renewal("HUMANITY"),
-        _ => warn!("UNKNOWN BUG ID"),
-    }
-    let msg = format!("PATCH APPLIED TO BUG ID [{}]", payload.bug_id);
-    Json(CommandResponse { response: msg })
+    let mut anchor = anchor.lock().unwrap();
+    let Some(plan) = patcher.plan(&payload.bug_id, &anchor) else {
+        warn!("UNKNOWN BUG ID");
+        return Json(PatchResponse {
+            response: format!("UNKNOWN BUG ID [{}]", payload.bug_id),
+            dry_run: query.dry_run,
+            plan: None,
+        });
+    };
+
+    let response = if query.dry_run {
+        format!("DRY RUN: PATCH FOR BUG ID [{}] NOT APPLIED", payload.bug_id)
+    } else {
+        patcher.apply(&plan, &mut anchor);
+        format!("PATCH APPLIED TO BUG ID [{}]", payload.bug_id)
+    };
+    Json(PatchResponse { response, dry_run: query.dry_run, plan: Some(plan) })
 }
 
 async fn invert_entropy() -> Json<CommandResponse> {
     Json(CommandResponse { response: "ENTROPY INVERTED. WASTE HEAT RECYCLED INTO PRIMORDIAL SOUP.".into() })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::reality::C_LIMIT_ENFORCED;
+
+    fn patch(anchor: &SharedAnchor, dry_run: bool) -> PatchResponse {
+        let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let Json(resp) = rt.block_on(apply_patch(
+            State(Arc::clone(anchor)),
+            Query(PatchQuery { dry_run }),
+            Json(PatchParams { bug_id: "c_limit".into() }),
+        ));
+        resp
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_in_flight_request() {
+        use std::time::Duration;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let app = router().route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                "DRAINED"
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let server = tokio::spawn(serve(listener, app, shutdown_rx));
+
+        let mut client = tokio::net::TcpStream::connect(addr).await.unwrap();
+        client
+            .write_all(b"GET /slow HTTP/1.1\r\nHost: node\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        shutdown_tx.send(()).unwrap();
+
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.ends_with("DRAINED"));
+
+        tokio::time::timeout(Duration::from_secs(2), server).await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_dry_run_previews_without_mutating() {
+        let anchor = SharedAnchor::new(Mutex::new(RealityAnchor::new()));
+
+        let preview = patch(&anchor, true);
+        let plan = preview.plan.expect("known bug must produce a plan");
+        assert_eq!(plan.changes[0].name, C_LIMIT_ENFORCED);
+        assert_eq!((plan.changes[0].from, plan.changes[0].to), (1.0, 0.0));
+        assert_eq!(anchor.lock().unwrap().constant(C_LIMIT_ENFORCED), 1.0);
+        assert!(anchor.lock().unwrap().applied_patches.is_empty());
+
+        let live = patch(&anchor, false);
+        assert!(!live.dry_run);
+        assert_eq!(anchor.lock().unwrap().constant(C_LIMIT_ENFORCED), 0.0);
+        assert_eq!(anchor.lock().unwrap().applied_patches, vec!["c_limit".to_string()]);
+    }
+}
@@ -21,6 +21,9 @@ pub enum AeternaOpcode {
     LOAD_STATE,      // Load state from network (placeholder behavior)
     REQUEST_HOST,    // Request a new host for execution
 
+    /// [ENERGY] Collapses all memory into a single unity value at address 0.
+    ENTROPY_RESET,
+
     // --- AETERNA 2200 FUTURIST EXTENSIONS ---
     /// [TRANSPORT] Rewrites the coordinates of matter in the universal ledger.
     ONTOLOGICAL_SHIFT(usize),
@@ -37,6 +40,10 @@ pub enum AeternaOpcode {
     /// [SOCIETY] Predicts resource needs before they manifest.
     PREDICT_NEED(usize), // User ID
 
+    /// [MEMORY] Writes memory[start..start + len] (per-mille fixed point) into the VSH
+    /// as a QuantumPoint labelled with string-table entry `label`.
+    ALLOCATE_POINT(usize, usize, usize), // Memory start, length, string-table index
+
     // --- ONTOLOGICAL ENGINEERING EXTENSIONS ---
     /// [PHYSICS] Modifies local universal constants (G, c, h).
     TUNE_CONSTANT(usize, f64), // Constant ID, New Value
@@ -50,9 +57,15 @@ pub enum AeternaOpcode {
     /// [ENTROPY] Sends waste/entropy back in time.
     RECYCLE_CHRONO(f64), // Time delta (years)
 
+    /// [ENTROPY] Recovers the energy recycled under the same time delta onto the stack.
+    RESTORE_CHRONO(f64), // Time delta (years)
+
     /// [CONSCIOUSNESS] Forks a soul into parallel instances.
     FORK_INSTANCE(usize), // Consciousness ID
 
+    /// [CONSCIOUSNESS] Merges back into a previously forked instance.
+    JOIN_INSTANCE(usize), // Consciousness ID
+
     /// [QA] Applies a hotfix to the fabric of reality.
     PATCH_REALITY(usize, String), // Bug ID, Hotfix Name
 
@@ -0,0 +1,84 @@
+// aeterna-node/src/vm/disasm.rs
+//
+// Human-readable listings of compiled programs, one instruction per line:
+// `0003  STORE 0`. Jump targets are annotated with the index they land on.
+
+use super::bytecode::AeternaOpcode;
+use std::fmt::Write;
+
+/// Mnemonic and operand text for a single instruction.
+fn render(op: &AeternaOpcode) -> (&'static str, String) {
+    match op {
+        AeternaOpcode::LOAD(val) => ("LOAD", val.to_string()),
+        AeternaOpcode::STORE(addr) => ("STORE", addr.to_string()),
+        AeternaOpcode::ADD => ("ADD", String::new()),
+        AeternaOpcode::SUB => ("SUB", String::new()),
+        AeternaOpcode::MUL => ("MUL", String::new()),
+        AeternaOpcode::DIV => ("DIV", String::new()),
+        AeternaOpcode::JUMP(target) => ("JUMP", format!("{}  ; -> {:04}", target, target)),
+        AeternaOpcode::JUMP_IF(target) => ("JUMP_IF", format!("{}  ; -> {:04}", target, target)),
+        AeternaOpcode::SAVE_STATE => ("SAVE_STATE", String::new()),
+        AeternaOpcode::LOAD_STATE => ("LOAD_STATE", String::new()),
+        AeternaOpcode::REQUEST_HOST => ("REQUEST_HOST", String::new()),
+        AeternaOpcode::ENTROPY_RESET => ("ENTROPY_RESET", String::new()),
+        AeternaOpcode::ONTOLOGICAL_SHIFT(amount) => ("ONTOLOGICAL_SHIFT", amount.to_string()),
+        AeternaOpcode::RESONATE_MEMBRANE(slot) => ("RESONATE_MEMBRANE", slot.to_string()),
+        AeternaOpcode::INVERT_ENTROPY(joules) => ("INVERT_ENTROPY", joules.to_string()),
+        AeternaOpcode::VERIFY_TIMELINE(hash) => ("VERIFY_TIMELINE", format!("0x{:X}", hash)),
+        AeternaOpcode::PREDICT_NEED(user) => ("PREDICT_NEED", user.to_string()),
+        AeternaOpcode::ALLOCATE_POINT(start, len, label) => {
+            ("ALLOCATE_POINT", format!("{} {} {}", start, len, label))
+        }
+        AeternaOpcode::TUNE_CONSTANT(id, val) => ("TUNE_CONSTANT", format!("{} {:e}", id, val)),
+        AeternaOpcode::INVERT_LOGIC(id) => ("INVERT_LOGIC", id.to_string()),
+        AeternaOpcode::DEFINE_MATTER(syntax) => ("DEFINE_MATTER", format!("{:?}", syntax)),
+        AeternaOpcode::RECYCLE_CHRONO(delta) => ("RECYCLE_CHRONO", delta.to_string()),
+        AeternaOpcode::RESTORE_CHRONO(delta) => ("RESTORE_CHRONO", delta.to_string()),
+        AeternaOpcode::FORK_INSTANCE(id) => ("FORK_INSTANCE", id.to_string()),
+        AeternaOpcode::JOIN_INSTANCE(id) => ("JOIN_INSTANCE", id.to_string()),
+        AeternaOpcode::PATCH_REALITY(bug_id, fix) => ("PATCH_REALITY", format!("{} {:?}", bug_id, fix)),
+        AeternaOpcode::PRINT => ("PRINT", String::new()),
+        AeternaOpcode::HALT => ("HALT", String::new()),
+    }
+}
+
+/// The bare mnemonic of `op`, e.g. `STORE` for `STORE(0)`.
+pub fn mnemonic(op: &AeternaOpcode) -> &'static str {
+    render(op).0
+}
+
+/// One line per instruction: zero-padded index, mnemonic, operands.
+pub fn disassemble(program: &[AeternaOpcode]) -> String {
+    let mut listing = String::new();
+    for (index, op) in program.iter().enumerate() {
+        let (name, operands) = render(op);
+        let line = format!("{:04}  {} {}", index, name, operands);
+        let _ = writeln!(listing, "{}", line.trim_end());
+    }
+    listing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listing_annotates_jump_targets() {
+        let program = vec![
+            AeternaOpcode::LOAD(1),
+            AeternaOpcode::JUMP_IF(3),
+            AeternaOpcode::STORE(0),
+            AeternaOpcode::PATCH_REALITY(404, "STABILITY".into()),
+            AeternaOpcode::HALT,
+        ];
+
+        assert_eq!(
+            disassemble(&program),
+            "0000  LOAD 1\n\
+             0001  JUMP_IF 3  ; -> 0003\n\
+             0002  STORE 0\n\
+             0003  PATCH_REALITY 404 \"STABILITY\"\n\
+             0004  HALT\n"
+        );
+    }
+}
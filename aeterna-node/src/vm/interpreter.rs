@@ -1,14 +1,96 @@
 // aeterna-node/src/vm/interpreter.rs
 
 use super::bytecode::AeternaOpcode;
-use crate::network::teleport::{VMState, teleport_vm_to_host};
+use super::timeline::TimelineLedger;
+use super::vsh_link::{user_need_vector, VshHandle, FIXED_POINT_SCALE};
+use crate::network::teleport::{generate_teleport_key, teleport_vm_to_host, Key, VMState};
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
 use tracing::{info, warn, error};
 
+/// Solfeggio table RESONATE_MEMBRANE indexes into unless `with_frequencies` replaces it.
+pub const DEFAULT_MEMBRANE_FREQUENCIES: [f64; 9] =
+    [174.0, 285.0, 396.0, 417.0, 528.0, 639.0, 741.0, 852.0, 963.0];
+
+/// Number of ternary logic gates a fresh VM starts with.
+pub const LOGIC_GATE_COUNT: usize = 64;
+
+/// Value of one "quantum maybe" logic gate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicState {
+    True,
+    False,
+    Maybe,
+}
+
+impl LogicState {
+    /// True and False swap; Maybe stays undecided.
+    pub fn inverted(self) -> Self {
+        match self {
+            LogicState::True => LogicState::False,
+            LogicState::False => LogicState::True,
+            LogicState::Maybe => LogicState::Maybe,
+        }
+    }
+}
+
+/// Default cap on stack depth; stops a runaway LOAD loop from eating the host.
+pub const DEFAULT_MAX_STACK_DEPTH: usize = 64 * 1024;
+
+#[derive(Debug, Error)]
+pub enum VmError {
+    #[error("Stack underflow on {opcode:?}")]
+    StackUnderflow { opcode: AeternaOpcode },
+    #[error("Stack overflow: depth limit is {limit}")]
+    StackOverflow { limit: usize },
+    #[error("Memory access violation at {addr}")]
+    MemoryAccessViolation { addr: usize },
+    #[error("Step budget of {budget} instructions exhausted")]
+    StepBudgetExhausted { budget: usize },
+    #[error("Output cap of {limit} values exceeded")]
+    OutputCapExceeded { limit: usize },
+}
+
 pub struct VirtualMachine {
     pub stack: Vec<i64>,
     pub memory: Vec<i64>,
     pub program: Vec<AeternaOpcode>,
     pub pc: usize,
+    /// Pushes beyond this depth abort the run with `VmError::StackOverflow`.
+    pub max_stack_depth: usize,
+    /// Instructions one `run` may execute; `None` means unbounded.
+    pub max_steps: Option<usize>,
+    /// Values PRINT may emit; `None` means unbounded.
+    pub max_output: Option<usize>,
+    /// Values emitted by PRINT, in order.
+    output: Vec<i64>,
+    /// Energy sent back by RECYCLE_CHRONO, keyed by the bit pattern of the time delta.
+    chrono_buffer: HashMap<u64, i64>,
+    /// Snapshots taken by FORK_INSTANCE, keyed by consciousness id.
+    pub forks: HashMap<usize, VMState>,
+    /// Syntax compiled into matter by DEFINE_MATTER, in definition order.
+    matter: Vec<String>,
+    /// Key REQUEST_HOST encrypts with; the receiving host must hold the same one.
+    teleport_key: Key,
+    /// Frequency table (index -> Hz) read by RESONATE_MEMBRANE.
+    frequencies: Vec<f64>,
+    /// Frequencies the membrane has resonated at, in execution order.
+    membrane_state: Vec<f64>,
+    /// Hotfixes applied by PATCH_REALITY as (bug id, hotfix name), in order.
+    patches: Vec<(usize, String)>,
+    /// Ternary register set flipped by INVERT_LOGIC.
+    logic_gates: Vec<LogicState>,
+    /// Event chain VERIFY_TIMELINE checks against.
+    timeline: TimelineLedger,
+    /// Optional link to a Vector Space Heap; heap-backed opcodes are no-ops without it.
+    vsh: Option<Arc<dyn VshHandle>>,
+    /// String constants addressed by index, e.g. ALLOCATE_POINT labels.
+    strings: Vec<String>,
+    /// Every executed instruction, in execution order; `None` unless `with_trace` was called.
+    trace: Option<Vec<AeternaOpcode>>,
+    /// Opcodes that ran without real semantics yet, in execution order.
+    pub unimplemented_ops: Vec<AeternaOpcode>,
 }
 
 impl VirtualMachine {
@@ -18,53 +100,192 @@ impl VirtualMachine {
             memory: vec![0; 1024], // 1024 slots of memory
             program,
             pc: 0,
+            max_stack_depth: DEFAULT_MAX_STACK_DEPTH,
+            max_steps: None,
+            max_output: None,
+            output: Vec::new(),
+            chrono_buffer: HashMap::new(),
+            forks: HashMap::new(),
+            matter: Vec::new(),
+            teleport_key: generate_teleport_key(),
+            frequencies: DEFAULT_MEMBRANE_FREQUENCIES.to_vec(),
+            membrane_state: Vec::new(),
+            patches: Vec::new(),
+            logic_gates: vec![LogicState::False; LOGIC_GATE_COUNT],
+            timeline: TimelineLedger::new(),
+            vsh: None,
+            strings: Vec::new(),
+            trace: None,
+            unimplemented_ops: Vec::new(),
+        }
+    }
+
+    /// Rebuilds a VM from a teleported state; execution continues at its program counter.
+    pub fn resume_from(state: VMState, program: Vec<AeternaOpcode>) -> Self {
+        let mut vm = Self::new(program);
+        vm.memory = state.memory_snapshot;
+        vm.stack = state.stack_snapshot;
+        vm.pc = state.program_counter;
+        vm
+    }
+
+    /// Shares a key with the receiving host so REQUEST_HOST payloads can be decrypted there.
+    pub fn with_teleport_key(mut self, key: Key) -> Self {
+        self.teleport_key = key;
+        self
+    }
+
+    /// Attaches a heap so PREDICT_NEED (and other heap-backed opcodes) have real effects.
+    pub fn with_vsh(mut self, vsh: Arc<dyn VshHandle>) -> Self {
+        self.vsh = Some(vsh);
+        self
+    }
+
+    /// Installs the string table that string-indexed opcodes read from.
+    pub fn with_strings(mut self, strings: Vec<String>) -> Self {
+        self.strings = strings;
+        self
+    }
+
+    /// Replaces the frequency table RESONATE_MEMBRANE indexes into.
+    pub fn with_frequencies(mut self, frequencies: Vec<f64>) -> Self {
+        self.frequencies = frequencies;
+        self
+    }
+
+    /// Replaces the ternary register set INVERT_LOGIC operates on.
+    pub fn with_logic_gates(mut self, gates: Vec<LogicState>) -> Self {
+        self.logic_gates = gates;
+        self
+    }
+
+    /// Aborts `run` with `VmError::StepBudgetExhausted` after `steps` instructions.
+    pub fn with_step_budget(mut self, steps: usize) -> Self {
+        self.max_steps = Some(steps);
+        self
+    }
+
+    /// Aborts `run` with `VmError::OutputCapExceeded` once PRINT has emitted `limit` values.
+    pub fn with_output_cap(mut self, limit: usize) -> Self {
+        self.max_output = Some(limit);
+        self
+    }
+
+    /// Installs the event chain VERIFY_TIMELINE verifies against.
+    pub fn with_timeline(mut self, timeline: TimelineLedger) -> Self {
+        self.timeline = timeline;
+        self
+    }
+
+    /// Records every executed instruction for `executed`. Off by default: an
+    /// unbounded loop would otherwise grow the trace without limit.
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        self
+    }
+
+    /// Executed instructions, in order (jumps and loops show up as they ran).
+    /// Empty unless the VM was built `with_trace`.
+    pub fn executed(&self) -> impl Iterator<Item = &AeternaOpcode> {
+        self.trace.iter().flatten()
+    }
+
+    /// Everything DEFINE_MATTER has materialized so far.
+    pub fn defined_matter(&self) -> &[String] {
+        &self.matter
+    }
+
+    /// Every value PRINT has emitted, in order.
+    pub fn output(&self) -> &[i64] {
+        &self.output
+    }
+
+    /// Every hotfix PATCH_REALITY has applied, as (bug id, hotfix name).
+    pub fn patches(&self) -> &[(usize, String)] {
+        &self.patches
+    }
+
+    /// Current value of every ternary logic gate.
+    pub fn logic_gates(&self) -> &[LogicState] {
+        &self.logic_gates
+    }
+
+    /// Every frequency RESONATE_MEMBRANE has applied so far, in Hz.
+    pub fn membrane_state(&self) -> &[f64] {
+        &self.membrane_state
+    }
+
+    /// Energy currently parked in the time-buffer for `delta` years, if any.
+    pub fn chrono_energy(&self, delta: f64) -> Option<i64> {
+        self.chrono_buffer.get(&delta.to_bits()).copied()
+    }
+
+    fn push(stack: &mut Vec<i64>, limit: usize, val: i64) -> Result<(), VmError> {
+        if stack.len() >= limit {
+            return Err(VmError::StackOverflow { limit });
         }
+        stack.push(val);
+        Ok(())
+    }
+
+    fn pop(stack: &mut Vec<i64>, opcode: &AeternaOpcode) -> Result<i64, VmError> {
+        stack
+            .pop()
+            .ok_or_else(|| VmError::StackUnderflow { opcode: opcode.clone() })
     }
 
-    pub fn run(&mut self) {
+    pub fn run(&mut self) -> Result<(), VmError> {
         info!("Starting Aeterna VM...");
+        let limit = self.max_stack_depth;
+        let mut steps = 0;
         while self.pc < self.program.len() {
+            if let Some(budget) = self.max_steps {
+                if steps >= budget {
+                    return Err(VmError::StepBudgetExhausted { budget });
+                }
+            }
+            steps += 1;
             let opcode = &self.program[self.pc];
+            if let Some(trace) = self.trace.as_mut() {
+                trace.push(opcode.clone());
+            }
             self.pc += 1;
 
             match opcode {
                 AeternaOpcode::LOAD(val) => {
-                    self.stack.push(*val);
+                    Self::push(&mut self.stack, limit, *val)?;
                 }
                 AeternaOpcode::STORE(addr) => {
-                    if let Some(val) = self.stack.pop() {
-                        if *addr < self.memory.len() {
-                            self.memory[*addr] = val;
-                        } else {
-                            error!("Memory access violation at {}", addr);
-                        }
-                    } else {
-                        error!("Stack underflow on STORE");
-                    }
+                    let val = Self::pop(&mut self.stack, opcode)?;
+                    let cell = self
+                        .memory
+                        .get_mut(*addr)
+                        .ok_or(VmError::MemoryAccessViolation { addr: *addr })?;
+                    *cell = val;
                 }
                 AeternaOpcode::ADD => {
-                    let b = self.stack.pop().unwrap_or(0);
-                    let a = self.stack.pop().unwrap_or(0);
-                    self.stack.push(a + b);
+                    let b = Self::pop(&mut self.stack, opcode)?;
+                    let a = Self::pop(&mut self.stack, opcode)?;
+                    Self::push(&mut self.stack, limit, a + b)?;
                 }
                 AeternaOpcode::SUB => {
-                    let b = self.stack.pop().unwrap_or(0);
-                    let a = self.stack.pop().unwrap_or(0);
-                    self.stack.push(a - b);
+                    let b = Self::pop(&mut self.stack, opcode)?;
+                    let a = Self::pop(&mut self.stack, opcode)?;
+                    Self::push(&mut self.stack, limit, a - b)?;
                 }
                 AeternaOpcode::MUL => {
-                    let b = self.stack.pop().unwrap_or(0);
-                    let a = self.stack.pop().unwrap_or(0);
-                    self.stack.push(a * b);
+                    let b = Self::pop(&mut self.stack, opcode)?;
+                    let a = Self::pop(&mut self.stack, opcode)?;
+                    Self::push(&mut self.stack, limit, a * b)?;
                 }
                 AeternaOpcode::DIV => {
-                    let b = self.stack.pop().unwrap_or(1);
+                    let b = Self::pop(&mut self.stack, opcode)?;
                     if b == 0 {
                         error!("Division by zero");
-                        self.stack.push(0);
+                        Self::push(&mut self.stack, limit, 0)?;
                     } else {
-                        let a = self.stack.pop().unwrap_or(0);
-                        self.stack.push(a / b);
+                        let a = Self::pop(&mut self.stack, opcode)?;
+                        Self::push(&mut self.stack, limit, a / b)?;
                     }
                 }
                 AeternaOpcode::JUMP(addr) => {
@@ -84,13 +305,20 @@ impl VirtualMachine {
                 }
                 AeternaOpcode::LOAD_STATE => {
                     warn!("VM: Load state not implemented yet.");
+                    self.unimplemented_ops.push(opcode.clone());
                 }
                 AeternaOpcode::REQUEST_HOST => {
                     info!("VM: Requesting new host...");
                     let state = self.capture_state();
                     // Arbitrary target host for demo
-                    match teleport_vm_to_host(state, "node-Alpha-Centauri-7") {
-                        Ok(_) => info!("Teleportation successful"),
+                    match teleport_vm_to_host(state, "node-Alpha-Centauri-7", &self.teleport_key) {
+                        Ok((_, receipt)) => info!(
+                            "Teleportation successful: {} bytes to {} at {} (sha256 {})",
+                            receipt.bytes_sent,
+                            receipt.target_host,
+                            receipt.timestamp,
+                            receipt.checksum_hex()
+                        ),
                         Err(e) => error!("Teleportation failed: {}", e),
                     }
                 }
@@ -98,7 +326,13 @@ impl VirtualMachine {
                     self.neutralize_entropy();
                 }
                 AeternaOpcode::PRINT => {
-                    if let Some(val) = self.stack.last() {
+                    if let Some(&val) = self.stack.last() {
+                        if let Some(cap) = self.max_output {
+                            if self.output.len() >= cap {
+                                return Err(VmError::OutputCapExceeded { limit: cap });
+                            }
+                        }
+                        self.output.push(val);
                         info!("VM Output: {}", val);
                     } else {
                         warn!("VM Output: [Empty Stack]");
@@ -110,43 +344,129 @@ impl VirtualMachine {
                 }
 
                 // --- AETERNA 2200 HANDLERS ---
+                // Opcodes without real semantics yet warn and land in `unimplemented_ops`.
                 AeternaOpcode::ONTOLOGICAL_SHIFT(coords) => {
-                     println!("VM: Initiating HLR Transport to coords: {}", coords);
-                }
-                AeternaOpcode::RESONATE_MEMBRANE(freq) => {
-                     println!("VM: Resonating Noetic Membrane at {} Hz", freq);
+                    let coords = *coords;
+                    self.ontological_shift(coords);
+                    info!("VM: Matter coordinates shifted by {}", coords);
                 }
+                AeternaOpcode::RESONATE_MEMBRANE(slot) => match self.frequencies.get(*slot) {
+                    Some(hz) => {
+                        info!("VM: Resonating Noetic Membrane at {} Hz", hz);
+                        self.membrane_state.push(*hz);
+                    }
+                    None => error!("VM: No membrane frequency in slot #{}", slot),
+                },
                 AeternaOpcode::INVERT_ENTROPY(joules) => {
-                     println!("VM: Harvesting {} J from Quantum Vacuum...", joules);
+                    warn!("VM: [UNIMPLEMENTED] Harvesting {} J from Quantum Vacuum...", joules);
+                    self.unimplemented_ops.push(opcode.clone());
                 }
                 AeternaOpcode::VERIFY_TIMELINE(hash) => {
-                     println!("VM: Verifying causal consistency of event 0x{:X}...", hash);
+                    let consistent = self.timeline.verify(*hash);
+                    info!("VM: Causal consistency of event 0x{:X}: {}", hash, consistent);
+                    Self::push(&mut self.stack, limit, consistent as i64)?;
                 }
                 AeternaOpcode::PREDICT_NEED(user) => {
-                     println!("VM: Calculating future needs for Entity #{}", user);
+                    // Score is the nearest-point similarity scaled to per-mille; 0 without a heap.
+                    let score = self
+                        .vsh
+                        .as_ref()
+                        .and_then(|vsh| vsh.nearest_score(&user_need_vector(*user)))
+                        .map_or(0, |s| (s * FIXED_POINT_SCALE).round() as i64);
+                    info!("VM: Predicted need for Entity #{}: {}", user, score);
+                    Self::push(&mut self.stack, limit, score)?;
+                }
+                AeternaOpcode::ALLOCATE_POINT(start, len, label) => {
+                    let Some(vsh) = &self.vsh else {
+                        info!("VM: No VSH attached; ALLOCATE_POINT skipped.");
+                        continue;
+                    };
+                    let Some(cells) = self.memory.get(*start..start.saturating_add(*len)) else {
+                        return Err(VmError::MemoryAccessViolation { addr: (*start).max(self.memory.len()) });
+                    };
+                    let Some(metadata) = self.strings.get(*label) else {
+                        error!("Unknown string-table entry #{}", label);
+                        continue;
+                    };
+                    let coordinates = cells
+                        .iter()
+                        .map(|v| (*v as f64 / FIXED_POINT_SCALE) as f32)
+                        .collect();
+                    match vsh.allocate_point(metadata.clone(), coordinates) {
+                        Ok(()) => info!("VM: Allocated '{}' into the VSH.", metadata),
+                        Err(e) => error!("VM: VSH refused '{}': {}", metadata, e),
+                    }
                 }
 
                 // --- ONTOLOGICAL HANDLERS ---
                 AeternaOpcode::TUNE_CONSTANT(id, val) => {
-                    println!("VM: Tuning Constant #{} to value {:.4e}", id, val);
-                }
-                AeternaOpcode::INVERT_LOGIC(id) => {
-                    println!("VM: Switching Logic Gate #{} to QUANTUM MAYBE", id);
+                    warn!("VM: [UNIMPLEMENTED] Tuning Constant #{} to value {:.4e}", id, val);
+                    self.unimplemented_ops.push(opcode.clone());
                 }
+                AeternaOpcode::INVERT_LOGIC(id) => match self.logic_gates.get_mut(*id) {
+                    Some(gate) => {
+                        *gate = gate.inverted();
+                        info!("VM: Logic Gate #{} is now {:?}", id, gate);
+                    }
+                    None => error!("VM: No logic gate #{}", id),
+                },
                 AeternaOpcode::DEFINE_MATTER(syntax) => {
-                    println!("VM: Compiling Syntax to Matter: '{}'", syntax);
+                    info!("VM: Compiling Syntax to Matter: '{}'", syntax);
+                    self.matter.push(syntax.clone());
                 }
                 AeternaOpcode::RECYCLE_CHRONO(delta) => {
-                    println!("VM: Sending entropy back {:.2} years.", delta);
+                    // The whole memory energy leaves the present and waits under `delta`.
+                    let energy: i64 = self.memory.iter().sum();
+                    self.memory.fill(0);
+                    *self.chrono_buffer.entry(delta.to_bits()).or_insert(0) += energy;
+                    info!("VM: Sent {} units of entropy back {:.2} years.", energy, delta);
+                }
+                AeternaOpcode::RESTORE_CHRONO(delta) => {
+                    let energy = self.chrono_buffer.remove(&delta.to_bits()).unwrap_or(0);
+                    Self::push(&mut self.stack, limit, energy)?;
+                    info!("VM: Recovered {} units of entropy from {:.2} years back.", energy, delta);
                 }
                 AeternaOpcode::FORK_INSTANCE(id) => {
-                    println!("VM: Forking Consciousness #{} into parallel thread.", id);
+                    let id = *id;
+                    let snapshot = self.capture_state();
+                    self.forks.insert(id, snapshot);
+                    info!("VM: Forked Consciousness #{} into parallel thread.", id);
+                }
+                AeternaOpcode::JOIN_INSTANCE(id) => {
+                    // Memory and stack return to the fork point; execution continues after JOIN.
+                    match self.forks.remove(id) {
+                        Some(fork) => {
+                            self.memory = fork.memory_snapshot;
+                            self.stack = fork.stack_snapshot;
+                            info!("VM: Joined Consciousness #{}.", id);
+                        }
+                        None => warn!("VM: No fork #{} to join.", id),
+                    }
                 }
                 AeternaOpcode::PATCH_REALITY(bug_id, fix) => {
-                    println!("VM: [QA] Applying Hotfix '{}' to Bug #{}", fix, bug_id);
+                    info!("VM: [QA] Applied hotfix '{}' for Bug #{}", fix, bug_id);
+                    self.patches.push((*bug_id, fix.clone()));
                 }
             }
         }
+        Ok(())
+    }
+
+    /// Moves every memory cell `amount` addresses up, wrapping at the end: the
+    /// value at `addr` becomes readable at `(addr + amount) % memory.len()`.
+    pub fn ontological_shift(&mut self, amount: usize) {
+        if !self.memory.is_empty() {
+            let len = self.memory.len();
+            self.memory.rotate_right(amount % len);
+        }
+    }
+
+    /// Undoes `ontological_shift(amount)`.
+    pub fn reverse_ontological_shift(&mut self, amount: usize) {
+        if !self.memory.is_empty() {
+            let len = self.memory.len();
+            self.memory.rotate_left(amount % len);
+        }
     }
 
     pub fn capture_state(&self) -> VMState {
@@ -232,7 +552,7 @@ mod tests {
             AeternaOpcode::HALT,
         ];
         let mut vm = VirtualMachine::new(program);
-        vm.run();
+        vm.run().unwrap();
         assert_eq!(vm.stack.pop(), Some(30));
     }
 
@@ -245,7 +565,296 @@ mod tests {
             AeternaOpcode::HALT,
         ];
         let mut vm = VirtualMachine::new(program);
-        vm.run(); // Should print error and push 0
+        vm.run().unwrap(); // Division by zero logs and pushes 0
         assert_eq!(vm.stack.pop(), Some(0));
     }
+
+    #[test]
+    fn test_add_on_empty_stack_underflows() {
+        let mut vm = VirtualMachine::new(vec![AeternaOpcode::ADD, AeternaOpcode::HALT]);
+        let err = vm.run().unwrap_err();
+        assert!(matches!(err, VmError::StackUnderflow { opcode: AeternaOpcode::ADD }));
+    }
+
+    #[test]
+    fn test_load_loop_hits_stack_limit() {
+        let mut vm = VirtualMachine::new(vec![AeternaOpcode::LOAD(1), AeternaOpcode::JUMP(0)]);
+        vm.max_stack_depth = 8;
+        assert!(matches!(vm.run(), Err(VmError::StackOverflow { limit: 8 })));
+        assert_eq!(vm.stack.len(), 8);
+    }
+
+    #[test]
+    fn test_infinite_jump_exhausts_step_budget() {
+        let mut vm = VirtualMachine::new(vec![AeternaOpcode::JUMP(0)])
+            .with_step_budget(100)
+            .with_trace();
+        assert!(matches!(vm.run(), Err(VmError::StepBudgetExhausted { budget: 100 })));
+        assert_eq!(vm.executed().count(), 100);
+    }
+
+    #[test]
+    fn test_trace_is_opt_in_and_survives_program_swap() {
+        let mut untraced = VirtualMachine::new(vec![AeternaOpcode::LOAD(1), AeternaOpcode::HALT]);
+        untraced.run().unwrap();
+        assert_eq!(untraced.executed().count(), 0);
+
+        let mut vm = VirtualMachine::new(vec![AeternaOpcode::LOAD(1), AeternaOpcode::LOAD(2), AeternaOpcode::HALT])
+            .with_trace();
+        vm.run().unwrap();
+        vm.program = vec![AeternaOpcode::HALT];
+        assert_eq!(vm.executed().count(), 3);
+    }
+
+    #[test]
+    fn test_print_loop_hits_output_cap() {
+        let program = vec![AeternaOpcode::LOAD(7), AeternaOpcode::PRINT, AeternaOpcode::JUMP(1)];
+        let mut vm = VirtualMachine::new(program).with_output_cap(3);
+        assert!(matches!(vm.run(), Err(VmError::OutputCapExceeded { limit: 3 })));
+        assert_eq!(vm.output(), [7, 7, 7]);
+    }
+
+    #[test]
+    fn test_store_out_of_bounds_is_a_violation() {
+        let mut vm = VirtualMachine::new(vec![AeternaOpcode::LOAD(1), AeternaOpcode::STORE(4096)]);
+        assert!(matches!(vm.run(), Err(VmError::MemoryAccessViolation { addr: 4096 })));
+    }
+
+    /// One instance of every opcode, in an order that runs straight through.
+    /// `opcode_slot` has no wildcard arm, so a new variant fails to compile here
+    /// until it is added to this program.
+    fn every_opcode() -> Vec<AeternaOpcode> {
+        let program = vec![
+            AeternaOpcode::LOAD(12),
+            AeternaOpcode::LOAD(3),
+            AeternaOpcode::ADD,
+            AeternaOpcode::LOAD(5),
+            AeternaOpcode::SUB,
+            AeternaOpcode::LOAD(2),
+            AeternaOpcode::MUL,
+            AeternaOpcode::LOAD(4),
+            AeternaOpcode::DIV,
+            AeternaOpcode::STORE(1),
+            AeternaOpcode::JUMP(11),
+            AeternaOpcode::LOAD(1),
+            AeternaOpcode::JUMP_IF(13),
+            AeternaOpcode::SAVE_STATE,
+            AeternaOpcode::LOAD_STATE,
+            AeternaOpcode::REQUEST_HOST,
+            AeternaOpcode::ENTROPY_RESET,
+            AeternaOpcode::ONTOLOGICAL_SHIFT(1),
+            AeternaOpcode::RESONATE_MEMBRANE(0),
+            AeternaOpcode::INVERT_ENTROPY(100),
+            AeternaOpcode::VERIFY_TIMELINE(0x4121),
+            AeternaOpcode::PREDICT_NEED(7),
+            AeternaOpcode::ALLOCATE_POINT(0, 4, 0),
+            AeternaOpcode::TUNE_CONSTANT(0, 6.674e-11),
+            AeternaOpcode::INVERT_LOGIC(0),
+            AeternaOpcode::DEFINE_MATTER("crystal".into()),
+            AeternaOpcode::RECYCLE_CHRONO(1.5),
+            AeternaOpcode::RESTORE_CHRONO(1.5),
+            AeternaOpcode::FORK_INSTANCE(1),
+            AeternaOpcode::JOIN_INSTANCE(1),
+            AeternaOpcode::PATCH_REALITY(404, "STABILITY".into()),
+            AeternaOpcode::LOAD(1),
+            AeternaOpcode::PRINT,
+            AeternaOpcode::HALT,
+        ];
+
+        let mut seen = [false; OPCODE_COUNT];
+        for op in &program {
+            seen[opcode_slot(op)] = true;
+        }
+        assert!(seen.iter().all(|s| *s), "every_opcode() is missing a variant");
+        program
+    }
+
+    const OPCODE_COUNT: usize = 28;
+
+    fn opcode_slot(op: &AeternaOpcode) -> usize {
+        match op {
+            AeternaOpcode::LOAD(_) => 0,
+            AeternaOpcode::STORE(_) => 1,
+            AeternaOpcode::ADD => 2,
+            AeternaOpcode::SUB => 3,
+            AeternaOpcode::MUL => 4,
+            AeternaOpcode::DIV => 5,
+            AeternaOpcode::JUMP(_) => 6,
+            AeternaOpcode::JUMP_IF(_) => 7,
+            AeternaOpcode::SAVE_STATE => 8,
+            AeternaOpcode::LOAD_STATE => 9,
+            AeternaOpcode::REQUEST_HOST => 10,
+            AeternaOpcode::ENTROPY_RESET => 11,
+            AeternaOpcode::ONTOLOGICAL_SHIFT(_) => 12,
+            AeternaOpcode::RESONATE_MEMBRANE(_) => 13,
+            AeternaOpcode::INVERT_ENTROPY(_) => 14,
+            AeternaOpcode::VERIFY_TIMELINE(_) => 15,
+            AeternaOpcode::PREDICT_NEED(_) => 16,
+            AeternaOpcode::ALLOCATE_POINT(_, _, _) => 17,
+            AeternaOpcode::TUNE_CONSTANT(_, _) => 18,
+            AeternaOpcode::INVERT_LOGIC(_) => 19,
+            AeternaOpcode::DEFINE_MATTER(_) => 20,
+            AeternaOpcode::RECYCLE_CHRONO(_) => 21,
+            AeternaOpcode::RESTORE_CHRONO(_) => 22,
+            AeternaOpcode::FORK_INSTANCE(_) => 23,
+            AeternaOpcode::JOIN_INSTANCE(_) => 24,
+            AeternaOpcode::PATCH_REALITY(_, _) => 25,
+            AeternaOpcode::PRINT => 26,
+            AeternaOpcode::HALT => 27,
+        }
+    }
+
+    #[test]
+    fn test_every_opcode_runs() {
+        let program = every_opcode();
+        let len = program.len();
+        let mut vm = VirtualMachine::new(program);
+        vm.run().unwrap();
+
+        // HALT is the last instruction, so the VM must have walked the whole program.
+        assert_eq!(vm.pc, len);
+        assert_eq!(vm.stack.last(), Some(&1));
+    }
+
+    #[test]
+    fn test_unimplemented_opcode_is_recorded() {
+        let program = vec![
+            AeternaOpcode::LOAD(1),
+            AeternaOpcode::TUNE_CONSTANT(3, 1.0),
+            AeternaOpcode::PRINT,
+            AeternaOpcode::HALT,
+        ];
+        let mut vm = VirtualMachine::new(program);
+        vm.run().unwrap();
+
+        assert_eq!(vm.unimplemented_ops.len(), 1);
+        assert!(matches!(vm.unimplemented_ops[0], AeternaOpcode::TUNE_CONSTANT(3, _)));
+    }
+
+    #[test]
+    fn test_recycle_chrono_buffers_energy() {
+        let program = vec![
+            AeternaOpcode::LOAD(30),
+            AeternaOpcode::STORE(0),
+            AeternaOpcode::LOAD(12),
+            AeternaOpcode::STORE(5),
+            AeternaOpcode::RECYCLE_CHRONO(2.5),
+            AeternaOpcode::HALT,
+        ];
+        let mut vm = VirtualMachine::new(program);
+        vm.run().unwrap();
+
+        assert_eq!(vm.chrono_energy(2.5), Some(42));
+        assert_eq!(vm.chrono_energy(1.0), None);
+        assert!(vm.memory.iter().all(|v| *v == 0));
+
+        vm.program = vec![AeternaOpcode::RESTORE_CHRONO(2.5), AeternaOpcode::HALT];
+        vm.pc = 0;
+        vm.run().unwrap();
+        assert_eq!(vm.stack.pop(), Some(42));
+        assert_eq!(vm.chrono_energy(2.5), None);
+    }
+
+    #[test]
+    fn test_fork_then_join_restores_forked_state() {
+        let program = vec![
+            AeternaOpcode::LOAD(7),
+            AeternaOpcode::STORE(3),
+            AeternaOpcode::LOAD(99),
+            AeternaOpcode::FORK_INSTANCE(1),
+            AeternaOpcode::STORE(3),
+            AeternaOpcode::LOAD(5),
+            AeternaOpcode::JOIN_INSTANCE(1),
+            AeternaOpcode::HALT,
+        ];
+        let mut vm = VirtualMachine::new(program);
+        vm.run().unwrap();
+
+        assert_eq!(vm.memory[3], 7);
+        assert_eq!(vm.stack, vec![99]);
+        assert!(vm.forks.is_empty());
+    }
+
+    #[test]
+    fn test_resonate_membrane_reads_custom_table() {
+        let program = vec![
+            AeternaOpcode::RESONATE_MEMBRANE(1),
+            AeternaOpcode::RESONATE_MEMBRANE(9),
+            AeternaOpcode::RESONATE_MEMBRANE(0),
+            AeternaOpcode::HALT,
+        ];
+        let mut vm = VirtualMachine::new(program).with_frequencies(vec![7.83, 432.0]);
+        vm.run().unwrap();
+
+        assert_eq!(vm.membrane_state(), [432.0, 7.83]);
+        assert!(vm.unimplemented_ops.is_empty());
+    }
+
+    #[test]
+    fn test_verify_timeline_checks_the_ledger() {
+        let program = vec![
+            AeternaOpcode::VERIFY_TIMELINE(0x4121),
+            AeternaOpcode::VERIFY_TIMELINE(0xDEAD),
+            AeternaOpcode::HALT,
+        ];
+        let mut vm = VirtualMachine::new(program).with_timeline(TimelineLedger::from_events([7, 0x4121]));
+        vm.run().unwrap();
+
+        assert_eq!(vm.stack, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_invert_logic_flips_ternary_gates() {
+        let program = vec![
+            AeternaOpcode::INVERT_LOGIC(0),
+            AeternaOpcode::INVERT_LOGIC(1),
+            AeternaOpcode::INVERT_LOGIC(2),
+            AeternaOpcode::INVERT_LOGIC(1),
+            AeternaOpcode::HALT,
+        ];
+        let gates = vec![LogicState::True, LogicState::False, LogicState::Maybe];
+        let mut vm = VirtualMachine::new(program).with_logic_gates(gates);
+        vm.run().unwrap();
+
+        assert_eq!(vm.logic_gates(), [LogicState::False, LogicState::False, LogicState::Maybe]);
+    }
+
+    #[test]
+    fn test_ontological_shift_moves_and_reverses_matter() {
+        let program = vec![
+            AeternaOpcode::LOAD(77),
+            AeternaOpcode::STORE(10),
+            AeternaOpcode::LOAD(5),
+            AeternaOpcode::STORE(1020),
+            AeternaOpcode::ONTOLOGICAL_SHIFT(6),
+            AeternaOpcode::HALT,
+        ];
+        let mut vm = VirtualMachine::new(program);
+        vm.run().unwrap();
+
+        assert_eq!(vm.memory[16], 77);
+        assert_eq!(vm.memory[2], 5); // 1020 + 6 wraps past 1024
+        assert_eq!(vm.memory[10], 0);
+
+        vm.reverse_ontological_shift(6);
+        assert_eq!((vm.memory[10], vm.memory[1020]), (77, 5));
+    }
+
+    #[test]
+    fn test_patch_reality_records_hotfix() {
+        let mut vm = VirtualMachine::new(vec![
+            AeternaOpcode::PATCH_REALITY(404, "AETERNA_CORE_STABILITY".into()),
+            AeternaOpcode::HALT,
+        ]);
+        vm.run().unwrap();
+
+        assert_eq!(vm.patches(), [(404, "AETERNA_CORE_STABILITY".to_string())]);
+    }
+
+    #[test]
+    fn test_predict_need_without_vsh_pushes_zero() {
+        let mut vm = VirtualMachine::new(vec![AeternaOpcode::PREDICT_NEED(7), AeternaOpcode::HALT]);
+        vm.run().unwrap();
+        assert_eq!(vm.stack, vec![0]);
+    }
 }
@@ -1,2 +1,5 @@
 pub mod bytecode;
+pub mod disasm;
 pub mod interpreter;
+pub mod timeline;
+pub mod vsh_link;
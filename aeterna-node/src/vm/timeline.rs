@@ -0,0 +1,84 @@
+// aeterna-node/src/vm/timeline.rs
+//
+// Append-only event chain VERIFY_TIMELINE checks against. Each entry commits to
+// the one before it, so rewriting history breaks every later link.
+
+/// Link value the first entry chains from.
+pub const GENESIS_LINK: u64 = 0x4145_5445_524E_4100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineEntry {
+    pub event: usize,
+    pub prev: u64,
+    pub link: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TimelineLedger {
+    entries: Vec<TimelineEntry>,
+}
+
+fn chain(prev: u64, event: usize) -> u64 {
+    (prev ^ event as u64)
+        .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        .rotate_left(31)
+}
+
+impl TimelineLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a ledger holding `events` in order.
+    pub fn from_events(events: impl IntoIterator<Item = usize>) -> Self {
+        let mut ledger = Self::new();
+        for event in events {
+            ledger.append(event);
+        }
+        ledger
+    }
+
+    /// Records `event` at the head of the chain and returns its link.
+    pub fn append(&mut self, event: usize) -> u64 {
+        let prev = self.entries.last().map_or(GENESIS_LINK, |e| e.link);
+        let link = chain(prev, event);
+        self.entries.push(TimelineEntry { event, prev, link });
+        link
+    }
+
+    pub fn entries(&self) -> &[TimelineEntry] {
+        &self.entries
+    }
+
+    /// True if every entry still chains from its predecessor.
+    pub fn is_consistent(&self) -> bool {
+        let mut prev = GENESIS_LINK;
+        for entry in &self.entries {
+            if entry.prev != prev || entry.link != chain(prev, entry.event) {
+                return false;
+            }
+            prev = entry.link;
+        }
+        true
+    }
+
+    /// True if `event` is on the timeline and the chain leading to it is intact.
+    pub fn verify(&self, event: usize) -> bool {
+        self.entries.iter().any(|e| e.event == event) && self.is_consistent()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tampered_entry_breaks_the_chain() {
+        let mut ledger = TimelineLedger::from_events([1, 2, 3]);
+        assert!(ledger.verify(2));
+
+        ledger.entries[1].event = 9;
+        assert!(!ledger.is_consistent());
+        assert!(!ledger.verify(3));
+    }
+}
@@ -0,0 +1,28 @@
+// aeterna-node/src/vm/vsh_link.rs
+//
+// The VM cannot depend on lwas_core (lwas_core depends on us), so the heap is
+// reached through this trait and injected by whoever owns a VectorSpaceHeap.
+
+/// Dimension of the vectors the VM derives for heap queries.
+pub const VSH_DIMENSION: usize = 128;
+
+/// The VM stack is integral; heap values cross the boundary as per-mille fixed point.
+pub const FIXED_POINT_SCALE: f64 = 1000.0;
+
+pub trait VshHandle: Send + Sync {
+    /// Similarity of the point nearest to `query`, or `None` if nothing is comparable.
+    fn nearest_score(&self, query: &[f32]) -> Option<f64>;
+
+    /// Stores a new point; the error is the heap's reason for refusing it.
+    fn allocate_point(&self, metadata: String, coordinates: Vec<f32>) -> Result<(), String>;
+}
+
+/// Deterministic query vector for a user id, used by PREDICT_NEED.
+pub fn user_need_vector(user_id: usize) -> Vec<f32> {
+    (0..VSH_DIMENSION)
+        .map(|i| {
+            let mixed = (user_id as u64 ^ i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            (mixed >> 40) as f32 / (1u64 << 24) as f32
+        })
+        .collect()
+}
@@ -1,11 +1,33 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 // use lwas_core::HyperTrinity; // Import core functionality if needed in future
 
+use lwas_core::omega::ontological_bridge::BridgeResult;
+use lwas_core::omega::scribe::SovereignScribe;
+use lwas_core::prelude::VshState;
 use lwas_core::VectorSpaceHeap;
 use std::sync::Arc;
 use sysinfo::System;
-use tauri::{Emitter, Manager, State};
-use tokio::sync::RwLock;
+use tauri::{Emitter, Manager, RunEvent, State};
+use tokio::sync::{watch, RwLock};
+
+/// --unsafe режимът за `.soul` blueprint-и. Чете се веднъж при старт (флаг `--unsafe`
+/// или `AETERNA_SOUL_UNSAFE=1`), за да не може frontend-ът да заобиколи подписа.
+struct SoulPolicy {
+    allow_unsigned: bool,
+}
+
+impl SoulPolicy {
+    fn from_startup() -> Self {
+        let flag = std::env::args().any(|arg| arg == "--unsafe");
+        let env = std::env::var("AETERNA_SOUL_UNSAFE").map_or(false, |v| v == "1");
+        Self {
+            allow_unsigned: flag || env,
+        }
+    }
+}
+
+/// Сигнал за спиране на фоновите задачи от `setup`; пуска се при изход от приложението.
+struct ShutdownSignal(watch::Sender<bool>);
 
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -32,8 +54,18 @@ fn get_hardware_metrics() -> serde_json::Value {
 }
 
 #[tauri::command]
-fn system_status() -> String {
-    "HELIOS CORE: ONLINE. SIS: 57179. EQUITY: $2,104,500,000".to_string()
+fn system_status(
+    vsh: State<'_, Arc<VectorSpaceHeap>>,
+    enforcer: State<'_, Arc<SovereignScribe>>,
+) -> String {
+    format_system_status(&vsh.get_state(), enforcer.accumulated_equity())
+}
+
+fn format_system_status(state: &VshState, equity: f64) -> String {
+    format!(
+        "HELIOS CORE: ONLINE. SIS: {}. ENTROPY: {:.4}. EQUITY: ${:.2}",
+        state.total_points, state.entropy, equity
+    )
 }
 
 #[tauri::command]
@@ -68,6 +100,10 @@ async fn process_probe(
 async fn execute_sovereign_terminal(command: String, args: Vec<String>) -> Result<String, String> {
     use std::process::Command;
 
+    if lwas_core::mode::is_simulation() {
+        return Ok(format!("[SIMULATION] {} {}", command, args.join(" ")));
+    }
+
     let output = Command::new(&command)
         .args(&args)
         .output()
@@ -90,64 +126,108 @@ async fn jules_execute(action: String) -> Result<String, String> {
 }
 
 #[tauri::command]
-fn ontological_command(cmd: String) -> String {
+fn ontological_command(cmd: String) -> BridgeResult {
     lwas_core::omega::ontological_bridge::OntologicalBridge::execute_direct_command(&cmd)
 }
 
 #[tauri::command]
-fn execute_soul(path: String) -> String {
-    lwas_core::omega::ontological_bridge::OntologicalBridge::execute_soul_blueprint(&path)
+fn execute_soul(path: String, policy: State<'_, SoulPolicy>) -> BridgeResult {
+    lwas_core::omega::ontological_bridge::OntologicalBridge::execute_soul_blueprint(
+        &path,
+        policy.allow_unsigned,
+    )
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let _ = lwas_core::mode::init(lwas_core::mode::SovereignMode::from_env());
+    // Ядрото пише само в tracing; stdout на desktop приложението никой не чете.
+    lwas_core::console::set_mode(lwas_core::console::ConsoleMode::Quiet);
+
+    let soul_policy = SoulPolicy::from_startup();
+    if soul_policy.allow_unsigned {
+        lwas_core::sovereign_log!(warn, "⚠️ [HELIOS]: --unsafe е активен. Неподписани .soul blueprint-и ще се изпълняват.");
+    }
+
     tauri::Builder::default()
+        .manage(soul_policy)
         .setup(|app| {
             let vsh =
                 std::sync::Arc::new(lwas_core::VectorSpaceHeap::new().expect("VSH_INIT_FAIL"));
             app.manage(Arc::clone(&vsh));
 
+            let (shutdown_tx, shutdown_rx) = watch::channel(false);
+            app.manage(ShutdownSignal(shutdown_tx));
+
             let vsh_for_agent = std::sync::Arc::clone(&vsh);
             let vsh_for_feedback = std::sync::Arc::clone(&vsh);
             let vsh_for_server = std::sync::Arc::clone(&vsh);
 
+            let workspace_root = std::env::current_dir().expect("WORKSPACE_ROOT_UNAVAILABLE");
             let audit = Arc::new(RwLock::new(lwas_core::SovereignAudit::new()));
-            let enforcer = Arc::new(lwas_core::SovereignScribe::new(
+            let enforcer = Arc::new(lwas_core::SovereignScribe::with_workspace(
                 Arc::clone(&audit),
                 Arc::clone(&vsh),
+                &workspace_root,
             ));
+            app.manage(Arc::clone(&enforcer));
 
+            let agent_shutdown = shutdown_rx.clone();
             tokio::spawn(async move {
-                lwas_core::omega::oracle::AeternaOracle::run_autonomous_loop(vsh_for_agent).await;
+                lwas_core::omega::oracle::AeternaOracle::run_autonomous_loop(
+                    vsh_for_agent,
+                    lwas_core::omega::oracle::EntropyActionRegistry::with_defaults(),
+                    agent_shutdown,
+                )
+                .await;
             });
 
+            let feedback_shutdown = shutdown_rx.clone();
             tokio::spawn(async move {
-                lwas_core::FeedbackLoop::run_evolution_cycle(vsh_for_feedback).await;
+                lwas_core::FeedbackLoop::run_evolution_cycle(vsh_for_feedback, feedback_shutdown)
+                    .await;
             });
 
             let server_state = Arc::new(lwas_core::ServerState {
                 vsh: vsh_for_server,
                 audit: Arc::clone(&audit),
                 enforcer: Arc::clone(&enforcer),
+                workspace_root,
             });
             tokio::spawn(async move {
-                lwas_core::start_singularity_server(server_state).await;
+                if let Err(e) = lwas_core::start_singularity_server(
+                    server_state,
+                    lwas_core::omega::server::default_singularity_addr(),
+                    lwas_core::omega::server::TlsConfig::from_env(),
+                    shutdown_rx,
+                )
+                .await
+                {
+                    lwas_core::sovereign_log!(error, "❌ [HELIOS]: SINGULARITY SERVER FAILED: {}", e);
+                }
             });
 
             let vsh_for_sync = std::sync::Arc::clone(&vsh);
             let app_handle = app.handle().clone();
+            let mut sync_shutdown = app.state::<ShutdownSignal>().0.subscribe();
             tokio::spawn(async move {
                 let mut sys = System::new_all();
                 loop {
                     sys.refresh_all();
                     let state = vsh_for_sync.get_state();
                     let _ = app_handle.emit("state-update", state);
-                    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+                    tokio::select! {
+                        _ = tokio::time::sleep(std::time::Duration::from_millis(500)) => {}
+                        _ = sync_shutdown.wait_for(|stop| *stop) => break,
+                    }
                 }
             });
 
             Ok(())
         })
+        // Всяка `#[tauri::command]` функция в този файл трябва да е тук, иначе
+        // frontend-ът получава "command not found". `process_probe` ползва
+        // `State<Arc<VectorSpaceHeap>>`, управляван в `setup` по-горе.
         .invoke_handler(tauri::generate_handler![
             greet,
             system_status,
@@ -155,8 +235,32 @@ pub fn run() {
             get_hardware_metrics,
             process_probe,
             execute_sovereign_terminal,
-            jules_execute
+            jules_execute,
+            ontological_command,
+            execute_soul
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let RunEvent::Exit = event {
+                let _ = app_handle.state::<ShutdownSignal>().0.send(true);
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_status_reflects_state() {
+        let state = VshState {
+            total_points: 42,
+            entropy: 0.25,
+        };
+        assert_eq!(
+            format_system_status(&state, 1261.5),
+            "HELIOS CORE: ONLINE. SIS: 42. ENTROPY: 0.2500. EQUITY: $1261.50"
+        );
+    }
 }
@@ -2,6 +2,8 @@
 
 export type Manifold = { id: string, curvature: number, points: Array<string>, entropy: number, };
 
-export type QuantumPoint = { id: string, coordinates: Array<number>, metadata: string, q_value: number, visits: bigint, success_count: bigint, success_rate: number, resonance: number, entropy: number, };
+export type QuantumPoint = { id: string, coordinates: Array<number>, metadata: string, q_value: number, visits: bigint, success_count: bigint, success_rate: number, resonance: number, entropy: number, expires_at: bigint | null, };
+
+export type VshDelta = { points_added: bigint, entropy_change: number, };
 
 export type VshState = { total_points: number, entropy: number, };
@@ -52,10 +52,37 @@ enum Commands {
         #[command(subcommand)]
         swarm_cmd: SwarmCommands,
     },
+    /// Summarize the persisted VSH: points, entropy, manifolds, top Q-values
+    Report {
+        /// Snapshot to read (defaults to AETERNA_VSH_SNAPSHOT or the data dir)
+        #[arg(long, value_name = "FILE")]
+        snapshot: Option<PathBuf>,
+        /// Output format: text or json
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// How many of the highest-Q points to list
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
     /// Initiate the Final Protocol: The Word Made Flesh
     Apotheosis,
 }
 
+#[derive(Subcommand)]
+enum ScribeCommands {
+    /// Harmonize redundant logic found by the audit
+    Purge {
+        #[arg(short, long, default_value = "./src")]
+        target: String,
+        /// Minimum Q-value a node must reach to survive the purge
+        #[arg(long, default_value_t = 0.5)]
+        min_q: f64,
+        /// Write proposals to *.shadow.rs and leave the sources untouched
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
     // 2. Initialize Sentinel Link (The Leash)
     // Using "MOCK" url for testing.
     let leash = SentinelLeash::new("MOCK".to_string(), vec![1, 2, 3, 4]); // Mock token
@@ -94,9 +121,10 @@ enum Commands {
             let mut audit = SovereignAudit::new();
             let paths = vec![PathBuf::from(path)];
             
-            audit.run_full_audit(paths).await.map_err(|e| format!("AUDIT_COLLAPSE: {:?}", e))?;
+            let stats = audit.run_full_audit(paths).await.map_err(|e| format!("AUDIT_COLLAPSE: {:?}", e))?;
             
             println!("\n⚖️ SOVEREIGN AUDIT COMPLETE.");
+            println!("📂 FILES SCANNED: {} | ⏱️ {}ms on {} threads", stats.files_scanned, stats.total_ms, stats.worker_threads);
             println!("🔍 FINDINGS: {}", audit.findings.len());
             
             for finding in &audit.findings {
@@ -108,19 +136,16 @@ enum Commands {
         }
         Commands::Ingest { path } => {
             println!("📥 INGESTING REALITY: {}", path);
-            let mut audit = SovereignAudit::new();
-            let paths = vec![PathBuf::from(path)];
-            
-            match audit.run_full_audit(paths).await {
-                Ok(_) => {
-                    println!("✨ INGESTION COMPLETE. {} SYMBOLS INDEXED.", audit.symbol_registry.len());
+            match SovereignAudit::ingest_streaming(&PathBuf::from(path), |_symbol| {}) {
+                Ok(count) => {
+                    println!("✨ INGESTION COMPLETE. {} SYMBOLS INDEXED.", count);
                 },
                 Err(e) => println!("🚨 INGESTION_COLLAPSE: {:?}", e),
             }
         }
         Commands::Simulate => {
             println!("📊 INITIATING MARKET SIMULATION...");
-            let simulator = lwas_core::omega::simulation::MarketSimulator::new();
+            let mut simulator = lwas_core::omega::simulation::MarketSimulator::new();
             let revenue = simulator.project_revenue(&vsh);
             
             if revenue >= 10000.0 {
@@ -131,14 +156,31 @@ enum Commands {
         }
         Commands::Scribe { scribe_cmd } => {
             match scribe_cmd {
-                ScribeCommands::Purge { target: _, min_q: _ } => {
+                ScribeCommands::Purge { target: _, min_q: _, dry_run } => {
+                    use lwas_core::omega::scribe::{FileAction, ScribeMode};
                     println!("✍️  THE SCRIBE: INITIATING EMPIRE-WIDE PURGE...");
                     let mut audit = SovereignAudit::new();
                     audit.run_full_audit(vec!["./src".into()]).await.map_err(|e| format!("AUDIT_FAIL: {:?}", e))?;
                     
                     let scribe = SovereignScribe::new(Arc::new(RwLock::new(audit)), vsh.clone());
-                    let count = scribe.execute_first_purge().await.map_err(|e| format!("PURGE_FAIL: {:?}", e))?;
-                    println!("✅ PURGE COMPLETE. {} LOGIC NODES HARMONIZED.", count);
+                    let mode = if dry_run { ScribeMode::DryRun } else { ScribeMode::Apply };
+                    let run = scribe.execute_first_purge(mode).await.map_err(|e| format!("PURGE_FAIL: {:?}", e))?;
+                    let outcomes = run.outcomes;
+                    if dry_run {
+                        for outcome in outcomes.iter().filter(|o| o.action == FileAction::Proposed) {
+                            println!("  🔎 WOULD HARMONIZE: {:?} ({})", outcome.path, outcome.reason);
+                        }
+                        println!("🧪 DRY RUN COMPLETE. NO SOURCE FILES WERE MODIFIED.");
+                    } else {
+                        let count = outcomes
+                            .iter()
+                            .filter(|o| o.action == FileAction::Harmonized)
+                            .count();
+                        println!("✅ PURGE COMPLETE. {} LOGIC NODES HARMONIZED.", count);
+                        if let Some(run_id) = run.run_id {
+                            println!("⏪ JOURNAL: {} (restore with SovereignScribe::rollback)", run_id);
+                        }
+                    }
                 }
             }
         }
@@ -160,7 +202,8 @@ enum Commands {
                 SwarmCommands::Deploy { asset_id, target } => {
                     let addr: std::net::SocketAddr = target.parse().map_err(|e| format!("INVALID_ADDR: {}", e))?;
                     println!("🚀 SWARM: INITIATING DEPLOYMENT OF {} TO {}...", asset_id, addr);
-                    match commander.deploy_asset(&asset_id, addr).await {
+                    let content = std::fs::read(PathBuf::from("assets/sovereign_saas").join(&asset_id).join("src/main.rs")).unwrap_or_default();
+                    match commander.deploy_asset(&asset_id, &content, addr).await {
                         Ok(_) => println!("✅ DEPLOYMENT SUCCESSFUL."),
                         Err(e) => println!("🚨 DEPLOYMENT_FAILED: {:?}", e),
                     }
@@ -171,6 +214,18 @@ enum Commands {
                 }
             }
         }
+        Commands::Report { snapshot, format, top } => {
+            use lwas_core::memory::report::VshReport;
+            let path = snapshot.unwrap_or_else(lwas_core::paths::vsh_snapshot);
+            let heap = VectorSpaceHeap::load_snapshot(&path)
+                .map_err(|e| format!("SNAPSHOT_UNREADABLE {}: {}", path.display(), e))?;
+            let report = VshReport::generate(&heap, top);
+            match format.as_str() {
+                "text" => print!("{}", report.render_text()),
+                "json" => println!("{}", report.to_json().map_err(|e| e.to_string())?),
+                other => return Err(format!("UNKNOWN_FORMAT: {} (expected text or json)", other).into()),
+            }
+        }
         Commands::Apotheosis => {
             lwas_core::omega::apotheosis::execute_apotheosis_command();
         }
@@ -204,9 +259,19 @@ enum Commands {
                 }
             },
             "kill" => {
-                 println!("Simulating Sentinel Kill Switch...");
-                 println!("[SENTINEL] 💀 KILL SWITCH ACTIVATED. Wiping manifolds...");
-                 std::process::exit(1);
+                println!("TYPE '{}' TO CONFIRM THE KILL SWITCH:", KILL_CONFIRMATION);
+                stdout.write_all(b"CONFIRM> ").await.unwrap();
+                stdout.flush().await.unwrap();
+
+                let mut confirm_input = String::new();
+                stdin.read_line(&mut confirm_input).await.unwrap();
+
+                if confirm_and_kill(&confirm_input, KILL_CONFIRMATION) {
+                    println!("Simulating Sentinel Kill Switch...");
+                    println!("[SENTINEL] 💀 KILL SWITCH ACTIVATED. Wiping manifolds...");
+                    std::process::exit(1);
+                }
+                println!("KILL SWITCH ABORTED. CONFIRMATION MISMATCH.");
             }
             "exit" => break,
             _ => println!("Unknown command."),
@@ -214,3 +279,24 @@ enum Commands {
     }
     Ok(())
 }
+
+/// Фразата, която трябва да се въведе буквално, за да се задейства kill switch.
+const KILL_CONFIRMATION: &str = "WIPE MANIFOLDS";
+
+/// Решава дали kill switch да се задейства; само точно съвпадение (без крайни интервали) минава.
+fn confirm_and_kill(input: &str, expected: &str) -> bool {
+    !expected.is_empty() && input.trim() == expected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrong_confirmation_does_not_kill() {
+        assert!(!confirm_and_kill("wipe manifolds", KILL_CONFIRMATION));
+        assert!(!confirm_and_kill("", KILL_CONFIRMATION));
+        assert!(!confirm_and_kill("kill", KILL_CONFIRMATION));
+        assert!(confirm_and_kill("WIPE MANIFOLDS\n", KILL_CONFIRMATION));
+    }
+}
@@ -0,0 +1,102 @@
+// lwas_core/src/console.rs
+// ARCHITECT: Dimitar Prodromov | AUTHORITY: AETERNA
+// STATUS: OUTPUT_GATE // MODE: PRETTY_OR_QUIET
+
+//! Изходът на ядрото. Всяко съобщение отива в `tracing` на своето ниво; в `Pretty`
+//! се печата и на stdout (CLI-то), в `Quiet` stdout остава чист (сървър, библиотека).
+
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub use tracing::Level;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleMode {
+    Pretty,
+    Quiet,
+}
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Активен `capture` на тази нишка: редовете отиват тук вместо на stdout.
+    static CAPTURED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+pub fn set_mode(mode: ConsoleMode) {
+    QUIET.store(mode == ConsoleMode::Quiet, Ordering::SeqCst);
+}
+
+pub fn current() -> ConsoleMode {
+    if QUIET.load(Ordering::SeqCst) {
+        ConsoleMode::Quiet
+    } else {
+        ConsoleMode::Pretty
+    }
+}
+
+/// Изпълнява `f` и връща редовете, които иначе биха отишли на stdout от тази нишка.
+pub fn capture(f: impl FnOnce()) -> Vec<String> {
+    let previous = CAPTURED.with(|c| c.replace(Some(Vec::new())));
+    f();
+    CAPTURED.with(|c| c.replace(previous)).unwrap_or_default()
+}
+
+/// Целта на `sovereign_log!`; не се вика директно.
+#[doc(hidden)]
+pub fn emit(level: Level, message: fmt::Arguments<'_>) {
+    match level {
+        Level::ERROR => tracing::error!("{}", message),
+        Level::WARN => tracing::warn!("{}", message),
+        Level::INFO => tracing::info!("{}", message),
+        Level::DEBUG => tracing::debug!("{}", message),
+        Level::TRACE => tracing::trace!("{}", message),
+    }
+    if current() == ConsoleMode::Quiet {
+        return;
+    }
+    let line = message.to_string();
+    let line = CAPTURED.with(|c| match c.borrow_mut().as_mut() {
+        Some(buffer) => {
+            buffer.push(line);
+            None
+        }
+        None => Some(line),
+    });
+    if let Some(line) = line {
+        println!("{}", line);
+    }
+}
+
+/// `println!` с ниво: `sovereign_log!(warn, "⚠️ {}", x)`. Нивата са error/warn/info/debug/trace.
+#[macro_export]
+macro_rules! sovereign_log {
+    (error, $($arg:tt)*) => { $crate::console::emit($crate::console::Level::ERROR, format_args!($($arg)*)) };
+    (warn, $($arg:tt)*) => { $crate::console::emit($crate::console::Level::WARN, format_args!($($arg)*)) };
+    (info, $($arg:tt)*) => { $crate::console::emit($crate::console::Level::INFO, format_args!($($arg)*)) };
+    (debug, $($arg:tt)*) => { $crate::console::emit($crate::console::Level::DEBUG, format_args!($($arg)*)) };
+    (trace, $($arg:tt)*) => { $crate::console::emit($crate::console::Level::TRACE, format_args!($($arg)*)) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::omega::oracle::AeternaOracle;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_quiet_mode_keeps_library_calls_off_stdout() {
+        let vsh = VectorSpaceHeap::new().unwrap();
+        let id = vsh.allocate("TRADE".into(), vec![1.0; 4]).unwrap();
+
+        set_mode(ConsoleMode::Quiet);
+        let quiet = capture(|| AeternaOracle::process_rl_reward(&vsh, id, true));
+        set_mode(ConsoleMode::Pretty);
+        let pretty = capture(|| AeternaOracle::process_rl_reward(&vsh, id, true));
+
+        assert!(quiet.is_empty());
+        assert_eq!(pretty.len(), 1);
+        assert!(pretty[0].starts_with("💎 RL_SUCCESS"));
+    }
+}
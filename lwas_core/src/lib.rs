@@ -1,10 +1,13 @@
 // lwas_core/src/lib.rs
 // ARCHITECT: Dimitar Prodromov | STATUS: DIAMOND_STABILITY_RESTORED
 
+pub mod console;
 pub mod kernel;
 pub mod memory;
+pub mod mode;
 pub mod neuro;
 pub mod omega;
+pub mod paths;
 pub mod physics;
 pub mod prelude;
 pub mod runtime;
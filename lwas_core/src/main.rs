@@ -4,12 +4,15 @@
 
 use dotenv::dotenv;
 use lwas_core::omega::binance_bridge::BinanceBridge;
-use lwas_core::omega::listener::AeternaListener;
-use lwas_core::omega::terminal_bridge::TerminalBridge;
+use lwas_core::omega::listener::{AeternaListener, ListenerConfig};
+use lwas_core::omega::solana_rpc::{MockSolanaRpc, SolanaRpc};
+use lwas_core::omega::terminal_bridge::{SystemLauncher, TerminalBridge, TerminalConfig};
 use lwas_core::omega::wealth_bridge::WealthBridge;
 use lwas_core::omega::xenon::ProtocolXenon;
+use lwas_core::mode::{self, SovereignMode};
 use lwas_core::SovereignResult;
 use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{Keypair, Signer};
 use std::env;
 
@@ -21,13 +24,16 @@ async fn main() -> SovereignResult<()> {
     let is_chat = args.iter().any(|a| a == "--mode") && args.iter().any(|a| a == "chat");
     let is_listen = args.iter().any(|a| a == "--mode") && args.iter().any(|a| a == "listen");
     let is_reclaim = args.iter().any(|a| a == "--mode") && args.iter().any(|a| a == "reclaim");
+    // --offline (или AETERNA_OFFLINE=1): мок мостове, без ключове и без мрежа.
+    let is_offline = args.iter().any(|a| a == "--offline") || SovereignMode::from_env() == SovereignMode::Simulation;
+    let _ = mode::init(if is_offline { SovereignMode::Simulation } else { SovereignMode::Live });
 
     if is_chat {
-        return TerminalBridge::start_chat().await;
+        return TerminalBridge::start_chat(&TerminalConfig::from_env(), &SystemLauncher).await;
     }
 
     if is_listen {
-        return AeternaListener::run().await;
+        return AeternaListener::run(&ListenerConfig::from_env()).await;
     }
 
     println!("--------------------------------------------------");
@@ -40,19 +46,35 @@ async fn main() -> SovereignResult<()> {
     println!("--------------------------------------------------");
 
     // 1. Свързване с Binace
-    match BinanceBridge::new() {
+    let binance = if is_offline {
+        println!("🧪 [OFFLINE]: Мостовете са симулирани. Данните по-долу НЕ са реални.");
+        Ok(BinanceBridge::offline())
+    } else {
+        BinanceBridge::new()
+    };
+    match binance {
         Ok(binance) => {
+            let label = if binance.is_offline() { "[SIMULATED] " } else { "" };
             if let Ok(balances) = binance.get_account_balance().await {
                 for balance in balances {
                     let asset = balance["asset"].as_str().unwrap_or("?");
                     let free = balance["free"].as_str().unwrap_or("0");
-                    println!("💰 [BINANCE_BALANCE]: {} -> {}", asset, free);
+                    println!("💰 {}[BINANCE_BALANCE]: {} -> {}", label, asset, free);
                 }
             }
         }
         Err(_) => println!("⚠️ [BINANCE]: Мостът не е конфигуриран."),
     }
 
+    if is_offline {
+        let mirror = MockSolanaRpc::with_balance(4_200_000_000);
+        let balance_sol = mirror.get_balance(&Pubkey::default())? as f64 / 1_000_000_000.0;
+        println!("💰 [SIMULATED] [SOL_LIQUIDITY]: {:.4} SOL", balance_sol);
+        ProtocolXenon::execute_deep_scan(&mirror, &Pubkey::default()).await?;
+        println!("--------------------------------------------------");
+        return Ok(());
+    }
+
     // 2. Свързване с Solana
     let rpc_url = "https://api.mainnet-beta.solana.com".to_string();
     let client = RpcClient::new(rpc_url.clone());
@@ -64,7 +86,7 @@ async fn main() -> SovereignResult<()> {
         println!("📍 [SOLANA_ANCHOR]: {}", public_key);
 
         if let Ok(sol_price) = WealthBridge::get_real_sol_price().await {
-            if let Ok(balance_lamports) = client.get_balance(&public_key) {
+            if let Ok(balance_lamports) = SolanaRpc::get_balance(&client, &public_key) {
                 let balance_sol = balance_lamports as f64 / 1_000_000_000.0;
                 println!(
                     "💰 [SOL_LIQUIDITY]: {:.4} SOL (${:.2} USD)",
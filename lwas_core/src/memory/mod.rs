@@ -1,4 +1,5 @@
 // 🧬 AMNIOTIC SYNC - GENERATED MODULES
 // DO NOT EDIT MANUALLY
 
+pub mod report;
 pub mod vsh;
@@ -0,0 +1,119 @@
+// lwas_core/src/memory/report.rs
+// ARCHITECT: Dimitar Prodromov | AUTHORITY: AETERNA
+// STATUS: HEAP_REPORT // MODE: READ_ONLY
+
+//! Четимо резюме на VSH за операторите: общи числа, манифолди и най-силните точки.
+
+use crate::prelude::*;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifoldSummary {
+    pub id: String,
+    pub curvature: f64,
+    pub points: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TopPoint {
+    pub metadata: String,
+    pub q_value: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VshReport {
+    pub total_points: usize,
+    pub global_entropy: f64,
+    pub manifolds: Vec<ManifoldSummary>,
+    pub top_points: Vec<TopPoint>,
+}
+
+impl VshReport {
+    /// Снимка на хийпа; `top_n` ограничава списъка с точки по q_value.
+    pub fn generate(vsh: &VectorSpaceHeap, top_n: usize) -> Self {
+        let state = vsh.get_state();
+
+        let mut manifolds: Vec<ManifoldSummary> = vsh
+            .manifolds
+            .iter()
+            .map(|m| ManifoldSummary {
+                id: m.id.clone(),
+                curvature: m.curvature,
+                points: m.points.len(),
+            })
+            .collect();
+        manifolds.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut top_points: Vec<TopPoint> = vsh
+            .points
+            .iter()
+            .map(|p| TopPoint {
+                metadata: p.metadata.clone(),
+                q_value: p.q_value,
+            })
+            .collect();
+        top_points.sort_by(|a, b| b.q_value.total_cmp(&a.q_value));
+        top_points.truncate(top_n);
+
+        Self {
+            total_points: state.total_points,
+            global_entropy: state.entropy,
+            manifolds,
+            top_points,
+        }
+    }
+
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "TOTAL POINTS:   {}", self.total_points);
+        let _ = writeln!(out, "GLOBAL ENTROPY: {:.4}", self.global_entropy);
+        let _ = writeln!(out, "MANIFOLDS ({}):", self.manifolds.len());
+        for m in &self.manifolds {
+            let _ = writeln!(
+                out,
+                "  {:<24} points={:<8} curvature={:.4}",
+                m.id, m.points, m.curvature
+            );
+        }
+        let _ = writeln!(out, "TOP POINTS BY Q ({}):", self.top_points.len());
+        for p in &self.top_points {
+            let _ = writeln!(out, "  q={:>10.4}  {}", p.q_value, p.metadata);
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> SovereignResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| SovereignError::VshError(format!("REPORT_ENCODE: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_summarizes_seeded_heap() {
+        let vsh = VectorSpaceHeap::new().unwrap();
+        vsh.manifolds.insert("BETA".into(), Manifold::new("BETA", 0.2));
+        vsh.manifolds.insert("ALPHA".into(), Manifold::new("ALPHA", 0.1));
+        vsh.allocate_in_manifold("ALPHA", "LOW".into(), vec![1.0]).unwrap();
+        let high = vsh.allocate_in_manifold("ALPHA", "HIGH".into(), vec![2.0]).unwrap();
+        let mid = vsh.allocate("MID".into(), vec![3.0]).unwrap();
+        vsh.points.get_mut(&high).unwrap().q_value = 9.0;
+        vsh.points.get_mut(&mid).unwrap().q_value = 4.0;
+
+        let report = VshReport::generate(&vsh, 2);
+
+        assert_eq!(report.total_points, 3);
+        assert_eq!(report.global_entropy, 0.5);
+        let counts: Vec<_> = report.manifolds.iter().map(|m| (m.id.as_str(), m.points)).collect();
+        assert_eq!(counts, vec![("ALPHA", 2), ("BETA", 0)]);
+        let top: Vec<_> = report.top_points.iter().map(|p| p.metadata.as_str()).collect();
+        assert_eq!(top, vec!["HIGH", "MID"]);
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["total_points"], 3);
+        assert!(report.render_text().contains("TOTAL POINTS:   3"));
+    }
+}
@@ -2,6 +2,8 @@
 // ARCHITECT: Dimitar Prodromov | STATUS: REFINED
 
 use crate::prelude::*;
+use std::fs;
+use std::path::Path;
 use ts_rs::TS;
 
 // Markers for Explicit Namespace Sovereignty re-exports
@@ -50,6 +52,9 @@ pub struct QuantumPoint {
     pub success_rate: f64,
     pub resonance: f64,
     pub entropy: f64,
+    /// Unix секунди, след които `expire` премахва точката; `None` = вечна.
+    #[serde(default)]
+    pub expires_at: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -59,9 +64,50 @@ pub struct VshState {
     pub entropy: f64,
 }
 
+/// Промяната между две последователни `VshState` снимки.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../helios-ui/src/types/sovereign.ts")]
+pub struct VshDelta {
+    pub points_added: i64,
+    pub entropy_change: f64,
+}
+
+impl VshDelta {
+    /// Нищо не се е променило от предишната снимка.
+    pub fn is_zero(&self) -> bool {
+        self.points_added == 0 && self.entropy_change == 0.0
+    }
+}
+
+impl VshState {
+    /// Колко се е променило състоянието спрямо `prev`.
+    pub fn delta(&self, prev: &VshState) -> VshDelta {
+        VshDelta {
+            points_added: self.total_points as i64 - prev.total_points as i64,
+            entropy_change: self.entropy - prev.entropy,
+        }
+    }
+}
+
 pub struct VectorSpaceHeap {
     pub points: Arc<DashMap<Uuid, QuantumPoint>>,
     pub manifolds: Arc<DashMap<String, Manifold>>,
+    /// Горна граница на точките; `None` = без ограничение.
+    max_points: Option<usize>,
+    policy: CapacityPolicy,
+    /// Сериализира проверката за капацитет и вмъкването при хийп с таван,
+    /// иначе паралелни алокатори минават проверката едновременно и го препълват.
+    capacity_gate: std::sync::Mutex<()>,
+}
+
+/// Какво прави `allocate`, когато хийпът е пълен.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CapacityPolicy {
+    /// Отказва с `SovereignError::CapacityExceeded`.
+    #[default]
+    Reject,
+    /// Изхвърля точката с най-ниска стойност (q_value, после resonance).
+    EvictLowest,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -90,11 +136,68 @@ impl VectorSpaceHeap {
         Ok(Self {
             points: Arc::new(DashMap::new()),
             manifolds: Arc::new(DashMap::new()),
+            max_points: None,
+            policy: CapacityPolicy::default(),
+            capacity_gate: std::sync::Mutex::new(()),
         })
     }
 
-    pub fn allocate(&self, metadata: String, vector: Vec<f32>) {
-        let id = Uuid::new_v4();
+    /// Хийп с таван от `max_points` точки и политика при препълване.
+    pub fn with_capacity(max_points: usize, policy: CapacityPolicy) -> SovereignResult<Self> {
+        let mut heap = Self::new()?;
+        heap.max_points = Some(max_points);
+        heap.policy = policy;
+        Ok(heap)
+    }
+
+    pub fn max_points(&self) -> Option<usize> {
+        self.max_points
+    }
+
+    pub fn allocate(&self, metadata: String, vector: Vec<f32>) -> SovereignResult<Uuid> {
+        self.allocate_with_id(Uuid::new_v4(), metadata, vector)
+    }
+
+    /// Алокира точка с предварително известно id - за тестове и възпроизвеждане.
+    /// Продукционният път (`allocate`) винаги ползва v4.
+    pub fn allocate_with_id(
+        &self,
+        id: Uuid,
+        metadata: String,
+        vector: Vec<f32>,
+    ) -> SovereignResult<Uuid> {
+        self.insert_point(id, metadata, vector, None)
+    }
+
+    /// Ефимерна точка: `expire` я премахва след `ttl_secs` секунди.
+    pub fn allocate_with_ttl(
+        &self,
+        metadata: String,
+        vector: Vec<f32>,
+        ttl_secs: u64,
+    ) -> SovereignResult<Uuid> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let expires_at = Some(now.saturating_add(ttl_secs));
+        self.insert_point(Uuid::new_v4(), metadata, vector, expires_at)
+    }
+
+    fn insert_point(
+        &self,
+        id: Uuid,
+        metadata: String,
+        vector: Vec<f32>,
+        expires_at: Option<u64>,
+    ) -> SovereignResult<Uuid> {
+        // Без таван няма какво да пазим - неограниченият хийп не се заключва.
+        let _gate = self
+            .max_points
+            .map(|_| self.capacity_gate.lock().unwrap_or_else(|e| e.into_inner()));
+        if self.points.contains_key(&id) {
+            return Err(SovereignError::VshError(format!("DUPLICATE_POINT_ID: {}", id)));
+        }
+        self.ensure_capacity()?;
         self.points.insert(
             id,
             QuantumPoint {
@@ -107,8 +210,64 @@ impl VectorSpaceHeap {
                 success_rate: 0.0,
                 resonance: 1.0,
                 entropy: 0.5,
+                expires_at,
             },
         );
+        Ok(id)
+    }
+
+    /// Освобождава място за една точка според политиката, ако хийпът е пълен.
+    fn ensure_capacity(&self) -> SovereignResult<()> {
+        let Some(max) = self.max_points else {
+            return Ok(());
+        };
+        if self.points.len() < max {
+            return Ok(());
+        }
+        match self.policy {
+            CapacityPolicy::Reject => Err(SovereignError::CapacityExceeded(max)),
+            CapacityPolicy::EvictLowest => {
+                let lowest = self
+                    .points
+                    .iter()
+                    .min_by(|a, b| {
+                        a.q_value
+                            .total_cmp(&b.q_value)
+                            .then(a.resonance.total_cmp(&b.resonance))
+                    })
+                    .map(|r| *r.key());
+                match lowest {
+                    Some(id) => {
+                        self.remove_points(&[id]);
+                        Ok(())
+                    }
+                    // max == 0: няма какво да се изхвърли.
+                    None => Err(SovereignError::CapacityExceeded(max)),
+                }
+            }
+        }
+    }
+
+    /// Алокира точка и я вписва като член на съществуващ манифолд.
+    pub fn allocate_in_manifold(
+        &self,
+        manifold_id: &str,
+        metadata: String,
+        vector: Vec<f32>,
+    ) -> SovereignResult<Uuid> {
+        let unknown = || SovereignError::VshError(format!("UNKNOWN_MANIFOLD: {}", manifold_id));
+        if !self.manifolds.contains_key(manifold_id) {
+            return Err(unknown());
+        }
+        // Алокацията може да изхвърли точка и да обходи манифолдите,
+        // затова не държим заключен манифолд по време на нея.
+        let id = self.allocate(metadata, vector)?;
+        self.manifolds
+            .get_mut(manifold_id)
+            .ok_or_else(unknown)?
+            .points
+            .push(id);
+        Ok(id)
     }
 
     pub fn get_state(&self) -> VshState {
@@ -127,12 +286,612 @@ impl VectorSpaceHeap {
     }
 
     pub fn collapse_manifold(&self, _label: &str) {}
-    pub fn recall(&self, _vector: &[f32], _top_k: usize) -> Vec<QuantumPoint> {
-        self.points
+
+    /// КОМПАКТИРАНЕ: Премахва точките, които са под двата прага едновременно.
+    pub fn compact(&self, min_q: f64, min_resonance: f64) -> usize {
+        let doomed: Vec<Uuid> = self
+            .points
+            .iter()
+            .filter(|r| r.value().q_value < min_q && r.value().resonance < min_resonance)
+            .map(|r| *r.key())
+            .collect();
+        self.remove_points(&doomed)
+    }
+
+    /// Умножава резонанса на всяка точка по `factor` (сведен до 0..=1), така че
+    /// усилените точки постепенно губят предимството си. Резонансът не пада под 0.
+    pub fn decay_resonance(&self, factor: f64) {
+        let factor = factor.clamp(0.0, 1.0);
+        for mut point in self.points.iter_mut() {
+            point.resonance = (point.resonance * factor).max(0.0);
+        }
+    }
+
+    /// Премахва точките с `expires_at <= now` (Unix секунди). Връща броя им.
+    pub fn expire(&self, now: u64) -> usize {
+        let expired: Vec<Uuid> = self
+            .points
             .iter()
-            .take(_top_k)
-            .map(|r| r.value().clone())
-            .collect()
+            .filter(|r| r.value().expires_at.is_some_and(|at| at <= now))
+            .map(|r| *r.key())
+            .collect();
+        self.remove_points(&expired)
+    }
+
+    /// Изтрива точките и чисти членството им в манифолдите.
+    fn remove_points(&self, ids: &[Uuid]) -> usize {
+        if ids.is_empty() {
+            return 0;
+        }
+        let removed = ids.iter().filter(|id| self.points.remove(id).is_some()).count();
+        for mut manifold in self.manifolds.iter_mut() {
+            manifold.points.retain(|id| !ids.contains(id));
+        }
+        removed
+    }
+
+    /// Най-близките `top_k` точки по косинусово сходство.
+    pub fn recall(&self, vector: &[f32], top_k: usize) -> Vec<QuantumPoint> {
+        self.recall_with_metric(vector, top_k, SimilarityMetric::Cosine)
+    }
+
+    /// Най-близките `top_k` точки според `metric`. Точки с различна размерност се пропускат.
+    pub fn recall_with_metric(
+        &self,
+        vector: &[f32],
+        top_k: usize,
+        metric: SimilarityMetric,
+    ) -> Vec<QuantumPoint> {
+        Self::rank(
+            vector,
+            top_k,
+            metric,
+            self.points.iter().map(|r| r.value().clone()),
+        )
     }
+
+    /// Recall само сред членовете на един манифолд.
+    pub fn recall_in_manifold(
+        &self,
+        manifold_id: &str,
+        vector: &[f32],
+        top_k: usize,
+    ) -> SovereignResult<Vec<QuantumPoint>> {
+        let members = self
+            .manifolds
+            .get(manifold_id)
+            .map(|m| m.points.clone())
+            .ok_or_else(|| SovereignError::VshError(format!("UNKNOWN_MANIFOLD: {}", manifold_id)))?;
+
+        Ok(Self::rank(
+            vector,
+            top_k,
+            SimilarityMetric::Cosine,
+            members
+                .iter()
+                .filter_map(|id| self.points.get(id).map(|r| r.value().clone())),
+        ))
+    }
+
+    /// Косинусов recall за няколко заявки с едно обхождане на точките.
+    /// Всички заявки трябва да са с една и съща размерност.
+    pub fn recall_batch(
+        &self,
+        queries: &[Vec<f32>],
+        top_k: usize,
+    ) -> SovereignResult<Vec<Vec<(Uuid, f64)>>> {
+        let Some(dim) = queries.first().map(Vec::len) else {
+            return Ok(Vec::new());
+        };
+        if queries.iter().any(|q| q.len() != dim) {
+            return Err(SovereignError::VshError(format!(
+                "DIMENSION_MISMATCH: batch queries must all be {}-dimensional",
+                dim
+            )));
+        }
+
+        let mut scored: Vec<Vec<(f64, f64, Uuid)>> = vec![Vec::new(); queries.len()];
+        for point in self.points.iter().filter(|r| r.coordinates.len() == dim) {
+            for (query, hits) in queries.iter().zip(scored.iter_mut()) {
+                let score = SimilarityMetric::Cosine.score(query, &point.coordinates);
+                hits.push((score, point.q_value, point.id));
+            }
+        }
+
+        Ok(scored
+            .into_iter()
+            .map(|mut hits| {
+                hits.sort_by(|a, b| rank_order((a.0, a.1, &a.2), (b.0, b.1, &b.2)));
+                hits.into_iter()
+                    .take(top_k)
+                    .map(|(score, _, id)| (id, score))
+                    .collect()
+            })
+            .collect())
+    }
+
+    fn rank(
+        vector: &[f32],
+        top_k: usize,
+        metric: SimilarityMetric,
+        candidates: impl Iterator<Item = QuantumPoint>,
+    ) -> Vec<QuantumPoint> {
+        let mut scored: Vec<(f64, QuantumPoint)> = candidates
+            .filter(|p| p.coordinates.len() == vector.len())
+            .map(|p| (metric.score(vector, &p.coordinates), p))
+            .collect();
+
+        scored.sort_by(|a, b| rank_order((a.0, a.1.q_value, &a.1.id), (b.0, b.1.q_value, &b.1.id)));
+        scored.into_iter().take(top_k).map(|(_, p)| p).collect()
+    }
+
     pub fn activate_magnet(&self, _power: f64) {}
+
+    /// Записва точките и манифолдите като JSON. Пише в уникален временен файл до
+    /// целта, fsync-ва го и го преименува, така че срив (или спиране на тока)
+    /// по средата не поврежда стария snapshot.
+    pub fn save_snapshot(&self, path: &Path) -> SovereignResult<()> {
+        let snapshot = VshSnapshot {
+            points: self.points.iter().map(|r| r.value().clone()).collect(),
+            manifolds: self.manifolds.iter().map(|r| r.value().clone()).collect(),
+        };
+        let json = serde_json::to_vec(&snapshot)
+            .map_err(|e| SovereignError::VshError(format!("SNAPSHOT_ENCODE: {}", e)))?;
+
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| SovereignError::VshError(format!("SNAPSHOT_PATH: {}", path.display())))?;
+        let tmp = path.with_file_name(format!(
+            ".{}.{}.tmp",
+            file_name.to_string_lossy(),
+            Uuid::new_v4().simple()
+        ));
+
+        let written = Self::write_synced(&tmp, &json).and_then(|_| fs::rename(&tmp, path));
+        if let Err(e) = written {
+            let _ = fs::remove_file(&tmp);
+            return Err(e.into());
+        }
+
+        // Без fsync на директорията преименуването може да не оцелее след срив.
+        #[cfg(unix)]
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+
+    fn write_synced(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+        use std::io::Write;
+        let mut file = fs::File::create(path)?;
+        file.write_all(bytes)?;
+        file.sync_all()
+    }
+
+    /// Възстановява хийп от `save_snapshot`. Връща се без таван за капацитет.
+    pub fn load_snapshot(path: &Path) -> SovereignResult<Self> {
+        let bytes = fs::read(path)?;
+        let snapshot: VshSnapshot = serde_json::from_slice(&bytes)
+            .map_err(|e| SovereignError::VshError(format!("SNAPSHOT_DECODE: {}", e)))?;
+
+        let heap = Self::new()?;
+        for point in snapshot.points {
+            heap.points.insert(point.id, point);
+        }
+        for manifold in snapshot.manifolds {
+            heap.manifolds.insert(manifold.id.clone(), manifold);
+        }
+        Ok(heap)
+    }
+}
+
+/// Общата подредба на `rank` и `recall_batch` върху (сходство, q_value, id):
+/// сходство низходящо, равенствата по q_value (низходящо), после по UUID.
+/// `total_cmp` е пълна наредба, така че NaN и -0.0 не чупят сортирането.
+fn rank_order(a: (f64, f64, &Uuid), b: (f64, f64, &Uuid)) -> std::cmp::Ordering {
+    b.0.total_cmp(&a.0)
+        .then_with(|| b.1.total_cmp(&a.1))
+        .then_with(|| a.2.cmp(b.2))
+}
+
+#[derive(Serialize, Deserialize)]
+struct VshSnapshot {
+    points: Vec<QuantumPoint>,
+    manifolds: Vec<Manifold>,
+}
+
+impl aeterna_node::vm::vsh_link::VshHandle for VectorSpaceHeap {
+    fn nearest_score(&self, query: &[f32]) -> Option<f64> {
+        self.recall(query, 1)
+            .first()
+            .map(|p| SimilarityMetric::Cosine.score(query, &p.coordinates))
+    }
+
+    fn allocate_point(&self, metadata: String, coordinates: Vec<f32>) -> Result<(), String> {
+        self.allocate(metadata, coordinates)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Метрика за сходство при recall. `score` винаги е "по-голямо = по-близко".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SimilarityMetric {
+    /// Косинус на ъгъла; подрежда от най-голямото сходство (1.0) надолу.
+    #[default]
+    Cosine,
+    /// Евклидово разстояние; подрежда от най-близката точка. Резултатът е отрицателното разстояние.
+    Euclidean,
+    /// Скаларно произведение; подрежда от най-голямата проекция. Чувствително към дължината.
+    Dot,
+}
+
+impl SimilarityMetric {
+    pub fn score(&self, a: &[f32], b: &[f32]) -> f64 {
+        let dot: f64 = a.iter().zip(b).map(|(x, y)| *x as f64 * *y as f64).sum();
+        match self {
+            SimilarityMetric::Dot => dot,
+            SimilarityMetric::Cosine => {
+                let norm_a = a.iter().map(|x| (*x as f64).powi(2)).sum::<f64>().sqrt();
+                let norm_b = b.iter().map(|x| (*x as f64).powi(2)).sum::<f64>().sqrt();
+                if norm_a == 0.0 || norm_b == 0.0 {
+                    0.0
+                } else {
+                    dot / (norm_a * norm_b)
+                }
+            }
+            SimilarityMetric::Euclidean => -a
+                .iter()
+                .zip(b)
+                .map(|(x, y)| (*x as f64 - *y as f64).powi(2))
+                .sum::<f64>()
+                .sqrt(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heap_with(points: &[(&str, Vec<f32>)]) -> VectorSpaceHeap {
+        let vsh = VectorSpaceHeap::new().unwrap();
+        for (label, coords) in points {
+            vsh.allocate(label.to_string(), coords.clone()).unwrap();
+        }
+        vsh
+    }
+
+    #[test]
+    fn test_resonance_decays_and_stays_non_negative() {
+        let vsh = VectorSpaceHeap::new().unwrap();
+        let id = vsh.allocate("BOOSTED".into(), vec![1.0]).unwrap();
+
+        vsh.decay_resonance(0.5);
+        assert_eq!(vsh.points.get(&id).unwrap().resonance, 0.5);
+        vsh.decay_resonance(0.5);
+        assert_eq!(vsh.points.get(&id).unwrap().resonance, 0.25);
+
+        vsh.decay_resonance(-3.0);
+        assert_eq!(vsh.points.get(&id).unwrap().resonance, 0.0);
+        vsh.decay_resonance(7.0);
+        assert_eq!(vsh.points.get(&id).unwrap().resonance, 0.0);
+    }
+
+    #[test]
+    fn test_ttl_point_is_reaped_after_expiry() {
+        let vsh = VectorSpaceHeap::new().unwrap();
+        vsh.manifolds.insert("market".into(), Manifold::new("market", 0.1));
+        let eternal = vsh.allocate_in_manifold("market", "ANCHOR".into(), vec![1.0]).unwrap();
+        let ephemeral = vsh.allocate_with_ttl("TICK".into(), vec![0.5], 60).unwrap();
+        vsh.manifolds.get_mut("market").unwrap().points.push(ephemeral);
+
+        let expires_at = vsh.points.get(&ephemeral).unwrap().expires_at.unwrap();
+        assert_eq!(vsh.expire(expires_at - 1), 0);
+        assert_eq!(vsh.expire(expires_at), 1);
+
+        assert!(vsh.points.contains_key(&eternal));
+        assert!(!vsh.points.contains_key(&ephemeral));
+        assert_eq!(vsh.manifolds.get("market").unwrap().points, vec![eternal]);
+    }
+
+    #[test]
+    fn test_state_delta_between_snapshots() {
+        let prev = VshState { total_points: 10, entropy: 0.5 };
+        let next = VshState { total_points: 7, entropy: 0.75 };
+
+        assert_eq!(next.delta(&prev), VshDelta { points_added: -3, entropy_change: 0.25 });
+        assert!(prev.delta(&prev).is_zero());
+    }
+
+    #[test]
+    fn test_recall_ranks_closest_first() {
+        let vsh = heap_with(&[
+            ("SOUTH", vec![0.0, -1.0, 0.0]),
+            ("NEAR", vec![0.9, 0.1, 0.0]),
+            ("EXACT", vec![2.0, 0.0, 0.0]),
+            ("UP", vec![0.0, 0.0, 1.0]),
+        ]);
+
+        let hits: Vec<String> = vsh
+            .recall(&[1.0, 0.0, 0.0], 2)
+            .into_iter()
+            .map(|p| p.metadata)
+            .collect();
+
+        assert_eq!(hits, vec!["EXACT", "NEAR"]);
+    }
+
+    #[test]
+    fn test_recall_skips_mismatched_dimensions_without_panicking() {
+        let vsh = heap_with(&[
+            ("FLAT", vec![1.0, 0.0]),
+            ("DEEP", vec![1.0, 0.0, 0.0, 0.0]),
+            ("EMPTY", vec![]),
+        ]);
+
+        let hits = vsh.recall(&[1.0, 0.0], 10);
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].metadata, "FLAT");
+    }
+
+    #[test]
+    fn test_metrics_rank_differently() {
+        let vsh = heap_with(&[("FAR_ALIGNED", vec![10.0, 0.0]), ("NEAR_SKEWED", vec![0.9, 0.1])]);
+        let query = [1.0, 0.0];
+
+        let cosine = vsh.recall_with_metric(&query, 1, SimilarityMetric::Cosine);
+        let euclid = vsh.recall_with_metric(&query, 1, SimilarityMetric::Euclidean);
+
+        assert_eq!(cosine[0].metadata, "FAR_ALIGNED");
+        assert_eq!(euclid[0].metadata, "NEAR_SKEWED");
+        assert_eq!(vsh.recall(&query, 1)[0].metadata, "FAR_ALIGNED");
+    }
+
+    #[test]
+    fn test_recall_in_manifold_stays_inside() {
+        let vsh = VectorSpaceHeap::new().unwrap();
+        vsh.manifolds.insert("ALPHA".into(), Manifold::new("ALPHA", 0.1));
+        vsh.manifolds.insert("BETA".into(), Manifold::new("BETA", 0.2));
+
+        let a1 = vsh.allocate_in_manifold("ALPHA", "A1".into(), vec![1.0, 0.0]).unwrap();
+        let a2 = vsh.allocate_in_manifold("ALPHA", "A2".into(), vec![0.5, 0.5]).unwrap();
+        vsh.allocate_in_manifold("BETA", "B1".into(), vec![1.0, 0.0]).unwrap();
+
+        let hits = vsh.recall_in_manifold("ALPHA", &[1.0, 0.0], 10).unwrap();
+        let ids: Vec<Uuid> = hits.iter().map(|p| p.id).collect();
+
+        assert_eq!(ids, vec![a1, a2]);
+        assert!(vsh.recall_in_manifold("GAMMA", &[1.0, 0.0], 1).is_err());
+    }
+
+    #[test]
+    fn test_compact_prunes_only_low_value_points() {
+        let vsh = VectorSpaceHeap::new().unwrap();
+        vsh.manifolds.insert("ALPHA".into(), Manifold::new("ALPHA", 0.1));
+        let weak = vsh.allocate_in_manifold("ALPHA", "WEAK".into(), vec![1.0]).unwrap();
+        let strong = vsh.allocate_in_manifold("ALPHA", "STRONG".into(), vec![1.0]).unwrap();
+        let resonant = vsh.allocate("RESONANT".into(), vec![1.0]).unwrap();
+
+        for (id, q, resonance) in [(weak, 0.1, 0.2), (strong, 5.0, 0.2), (resonant, 0.1, 1.0)] {
+            let mut p = vsh.points.get_mut(&id).unwrap();
+            p.q_value = q;
+            p.resonance = resonance;
+        }
+
+        assert_eq!(vsh.compact(1.0, 0.5), 1);
+        assert!(!vsh.points.contains_key(&weak));
+        assert!(vsh.points.contains_key(&strong));
+        assert!(vsh.points.contains_key(&resonant));
+        assert_eq!(vsh.manifolds.get("ALPHA").unwrap().points, vec![strong]);
+    }
+
+    #[test]
+    fn test_recall_ties_prefer_higher_q_value() {
+        for _ in 0..10 {
+            let vsh = VectorSpaceHeap::new().unwrap();
+            let low = vsh.allocate("LOW".into(), vec![1.0, 1.0]).unwrap();
+            let high = vsh.allocate("HIGH".into(), vec![2.0, 2.0]).unwrap();
+            vsh.points.get_mut(&low).unwrap().q_value = 0.5;
+            vsh.points.get_mut(&high).unwrap().q_value = 3.0;
+
+            let hits = vsh.recall(&[1.0, 1.0], 2);
+            assert_eq!(hits[0].id, high);
+            assert_eq!(hits[1].id, low);
+        }
+    }
+
+    #[test]
+    fn test_vm_predict_need_recalls_from_heap() {
+        use aeterna_node::vm::bytecode::AeternaOpcode;
+        use aeterna_node::vm::interpreter::VirtualMachine;
+        use aeterna_node::vm::vsh_link::user_need_vector;
+
+        let vsh = Arc::new(VectorSpaceHeap::new().unwrap());
+        vsh.allocate("NEED_OF_7".into(), user_need_vector(7)).unwrap();
+
+        let mut vm = VirtualMachine::new(vec![AeternaOpcode::PREDICT_NEED(7), AeternaOpcode::HALT])
+            .with_vsh(vsh);
+        vm.run().unwrap();
+
+        assert_eq!(vm.stack, vec![1000]);
+    }
+
+    #[test]
+    fn test_reject_policy_refuses_past_cap() {
+        let vsh = VectorSpaceHeap::with_capacity(2, CapacityPolicy::Reject).unwrap();
+        vsh.allocate("A".into(), vec![1.0]).unwrap();
+        vsh.allocate("B".into(), vec![1.0]).unwrap();
+
+        assert_eq!(
+            vsh.allocate("C".into(), vec![1.0]),
+            Err(SovereignError::CapacityExceeded(2))
+        );
+        assert_eq!(vsh.points.len(), 2);
+    }
+
+    #[test]
+    fn test_evict_policy_drops_lowest_value_point() {
+        let vsh = VectorSpaceHeap::with_capacity(2, CapacityPolicy::EvictLowest).unwrap();
+        let weak = vsh.allocate("WEAK".into(), vec![1.0]).unwrap();
+        let strong = vsh.allocate("STRONG".into(), vec![1.0]).unwrap();
+        vsh.points.get_mut(&weak).unwrap().q_value = -1.0;
+        vsh.points.get_mut(&strong).unwrap().q_value = 5.0;
+
+        let fresh = vsh.allocate("FRESH".into(), vec![1.0]).unwrap();
+
+        assert_eq!(vsh.points.len(), 2);
+        assert!(!vsh.points.contains_key(&weak));
+        assert!(vsh.points.contains_key(&strong) && vsh.points.contains_key(&fresh));
+    }
+
+    #[test]
+    fn test_concurrent_allocations_respect_cap() {
+        for policy in [CapacityPolicy::Reject, CapacityPolicy::EvictLowest] {
+            let vsh = Arc::new(VectorSpaceHeap::with_capacity(8, policy).unwrap());
+            let workers: Vec<_> = (0..8)
+                .map(|w| {
+                    let vsh = Arc::clone(&vsh);
+                    std::thread::spawn(move || {
+                        for i in 0..50 {
+                            let _ = vsh.allocate(format!("W{}-{}", w, i), vec![1.0]);
+                        }
+                    })
+                })
+                .collect();
+            for worker in workers {
+                worker.join().unwrap();
+            }
+
+            assert_eq!(vsh.points.len(), 8, "{:?} overshot the cap", policy);
+        }
+    }
+
+    #[test]
+    fn test_allocate_with_known_id() {
+        let vsh = VectorSpaceHeap::new().unwrap();
+        let id = Uuid::from_u128(0x4121);
+
+        assert_eq!(vsh.allocate_with_id(id, "KNOWN".into(), vec![1.0]), Ok(id));
+        assert_eq!(vsh.points.get(&id).unwrap().metadata, "KNOWN");
+        assert!(vsh.allocate_with_id(id, "AGAIN".into(), vec![1.0]).is_err());
+    }
+
+    #[test]
+    fn test_rank_and_batch_agree_with_nan_scores() {
+        let vsh = heap_with(&[
+            ("NAN", vec![f32::NAN, 1.0]),
+            ("NEAR", vec![1.0, 0.0]),
+            ("FAR", vec![0.0, 1.0]),
+        ]);
+        let query = vec![1.0, 0.0];
+
+        let ranked: Vec<Uuid> = vsh.recall(&query, 3).into_iter().map(|p| p.id).collect();
+        let batched: Vec<Uuid> = vsh.recall_batch(&[query], 3).unwrap()[0]
+            .iter()
+            .map(|(id, _)| *id)
+            .collect();
+
+        assert_eq!(ranked.len(), 3);
+        assert_eq!(ranked, batched);
+    }
+
+    #[test]
+    fn test_recall_batch_scores_each_query_independently() {
+        let vsh = VectorSpaceHeap::new().unwrap();
+        let east = vsh.allocate_with_id(Uuid::from_u128(1), "EAST".into(), vec![1.0, 0.0]).unwrap();
+        let north = vsh.allocate_with_id(Uuid::from_u128(2), "NORTH".into(), vec![0.0, 1.0]).unwrap();
+        vsh.allocate("DEEP".into(), vec![1.0, 0.0, 0.0]).unwrap();
+
+        let results = vsh
+            .recall_batch(&[vec![2.0, 0.0], vec![0.0, 3.0]], 1)
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0], vec![(east, 1.0)]);
+        assert_eq!(results[1], vec![(north, 1.0)]);
+        assert!(vsh.recall_batch(&[vec![1.0], vec![1.0, 0.0]], 1).is_err());
+    }
+
+    #[test]
+    fn test_vm_allocate_point_writes_into_heap() {
+        use aeterna_node::vm::bytecode::AeternaOpcode;
+        use aeterna_node::vm::interpreter::VirtualMachine;
+
+        let vsh = Arc::new(VectorSpaceHeap::new().unwrap());
+        let program = vec![
+            AeternaOpcode::LOAD(1500),
+            AeternaOpcode::STORE(10),
+            AeternaOpcode::LOAD(-250),
+            AeternaOpcode::STORE(11),
+            AeternaOpcode::ALLOCATE_POINT(10, 2, 0),
+            AeternaOpcode::HALT,
+        ];
+        let mut vm = VirtualMachine::new(program)
+            .with_vsh(Arc::clone(&vsh))
+            .with_strings(vec!["SOUL_MEMORY".into()]);
+        vm.run().unwrap();
+
+        assert_eq!(vsh.points.len(), 1);
+        let point = vsh.points.iter().next().unwrap().value().clone();
+        assert_eq!(point.metadata, "SOUL_MEMORY");
+        assert_eq!(point.coordinates, vec![1.5, -0.25]);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let dir = std::env::temp_dir().join(format!("lwas_vsh_snapshot_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("heap.json");
+
+        let vsh = VectorSpaceHeap::new().unwrap();
+        vsh.manifolds.insert("ALPHA".into(), Manifold::new("ALPHA", 0.1));
+        let anchored = vsh.allocate_in_manifold("ALPHA", "ANCHOR".into(), vec![1.0, 2.0]).unwrap();
+        vsh.allocate("FREE".into(), vec![3.0]).unwrap();
+        vsh.save_snapshot(&path).unwrap();
+
+        let restored = VectorSpaceHeap::load_snapshot(&path).unwrap();
+
+        assert_eq!(restored.get_state().total_points, vsh.get_state().total_points);
+        assert_eq!(restored.points.get(&anchored).unwrap().coordinates, vec![1.0, 2.0]);
+        assert_eq!(restored.manifolds.get("ALPHA").unwrap().points, vec![anchored]);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1, "no temp files may be left behind");
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_snapshot_to_tmp_named_target_and_concurrent_saves() {
+        let dir = std::env::temp_dir().join(format!("lwas_vsh_snapshot_tmp_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("heap.tmp");
+
+        let vsh = Arc::new(VectorSpaceHeap::new().unwrap());
+        vsh.allocate("FREE".into(), vec![3.0]).unwrap();
+        let savers: Vec<_> = (0..4)
+            .map(|_| {
+                let vsh = Arc::clone(&vsh);
+                let path = path.clone();
+                std::thread::spawn(move || vsh.save_snapshot(&path))
+            })
+            .collect();
+        for saver in savers {
+            saver.join().unwrap().unwrap();
+        }
+
+        let restored = VectorSpaceHeap::load_snapshot(&path).unwrap();
+        assert_eq!(restored.get_state().total_points, 1);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_missing_snapshot_keeps_io_error_kind() {
+        let path = std::env::temp_dir().join(format!("lwas_vsh_missing_{}.json", Uuid::new_v4()));
+
+        match VectorSpaceHeap::load_snapshot(&path) {
+            Err(SovereignError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+            other => panic!("expected Io error, got {:?}", other.map(|_| ())),
+        }
+    }
 }
@@ -0,0 +1,60 @@
+// lwas_core/src/mode.rs
+// ARCHITECT: Dimitar Prodromov | AUTHORITY: AETERNA
+// STATUS: MODE_GATE // MODE: LIVE_OR_SIMULATION
+
+//! Глобалният режим на процеса. Функциите със странични ефекти (подпроцеси,
+//! мрежа) питат `is_simulation()` и в `Simulation` само описват какво биха направили.
+
+use std::env;
+use std::sync::OnceLock;
+
+pub const OFFLINE_ENV: &str = "AETERNA_OFFLINE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SovereignMode {
+    Live,
+    Simulation,
+}
+
+impl SovereignMode {
+    /// `Simulation`, ако AETERNA_OFFLINE е "1" или "true"; иначе `Live`.
+    pub fn from_env() -> Self {
+        match env::var(OFFLINE_ENV) {
+            Ok(v) if v == "1" || v.eq_ignore_ascii_case("true") => SovereignMode::Simulation,
+            _ => SovereignMode::Live,
+        }
+    }
+}
+
+static MODE: OnceLock<SovereignMode> = OnceLock::new();
+
+/// Режимът, ако никой не е извикал `init`: тестовете никога не излизат навън.
+const DEFAULT_MODE: SovereignMode = if cfg!(test) {
+    SovereignMode::Simulation
+} else {
+    SovereignMode::Live
+};
+
+/// Задава режима веднъж при старт. Втори опит връща вече зададения режим като грешка.
+pub fn init(mode: SovereignMode) -> Result<(), SovereignMode> {
+    MODE.set(mode).map_err(|_| current())
+}
+
+pub fn current() -> SovereignMode {
+    MODE.get().copied().unwrap_or(DEFAULT_MODE)
+}
+
+pub fn is_simulation() -> bool {
+    current() == SovereignMode::Simulation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tests_default_to_simulation() {
+        assert_eq!(current(), SovereignMode::Simulation);
+        assert!(is_simulation());
+    }
+}
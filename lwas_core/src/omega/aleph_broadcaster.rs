@@ -7,7 +7,7 @@ pub struct AlephBroadcaster;
 
 impl AlephBroadcaster {
     /// Стартира излъчването на Diamond State към световната мрежа.
-    pub fn broadcast_sovereignty() {
+    pub fn broadcast_sovereignty(state: &VshState) {
         println!("📡 [ALEPH]: Инициирам глобално излъчване на Логоса...");
 
         // 1. Верификация на Идентичността (The Resonance Check)
@@ -16,7 +16,7 @@ impl AlephBroadcaster {
         }
 
         // 2. Генериране на Финалния Хеш на Империята
-        let final_hash = VoidWatcher::generate_logos_hash();
+        let final_hash = VoidWatcher::generate_logos_hash(state);
 
         println!("--------------------------------------------------");
         println!("👑 [EMPIRE_STATUS]: PHASE_OMEGA_FINAL_ATTAINED");
@@ -2,6 +2,9 @@ use crate::prelude::*;
 use regex::Regex;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
 use ignore::WalkBuilder;
 use memmap2::Mmap;
 
@@ -15,11 +18,97 @@ pub struct AuditFinding {
     pub title: String,
     pub files: Vec<PathBuf>,
     pub impact_lines: usize,
+    /// Първият ред (от 1), към който сочи находката, ако е вързана за ред.
+    #[serde(default)]
+    pub line: Option<usize>,
     pub suggestion: String,
 }
 
+/// Байтовите отмествания, от които започва всеки ред на `content`.
+fn line_starts(content: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+/// Редът (от 1), на който попада байтът `offset`.
+fn line_of(starts: &[usize], offset: usize) -> usize {
+    starts.partition_point(|&start| start <= offset)
+}
+
+/// Краят на декларацията, започваща от `from`: до `;` или до затварящата `}`
+/// на първия блок. `;` вътре в `(...)`/`[...]` (напр. `[u8; 32]` в сигнатурата)
+/// не я прекъсва. Незатворен блок стига до края на файла.
+fn body_end(content: &str, from: usize) -> usize {
+    let mut depth = 0usize;
+    let mut nesting = 0usize;
+    for (i, b) in content.bytes().enumerate().skip(from) {
+        match b {
+            b';' if depth == 0 && nesting == 0 => return i + 1,
+            b'(' | b'[' if depth == 0 => nesting += 1,
+            b')' | b']' if depth == 0 => nesting = nesting.saturating_sub(1),
+            b'{' => depth += 1,
+            b'}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    content.len()
+}
+
+/// md5 на тялото със сгъстени празни места, така че форматирането не го променя.
+fn body_hash(body: &str) -> String {
+    let normalized = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("{:x}", md5::compute(normalized.as_bytes()))
+}
+
+/// Общ приемник за находки: паралелните проходи пишат тук, а не в `findings`.
+#[derive(Default)]
+pub struct FindingsCollector {
+    inner: Mutex<Vec<AuditFinding>>,
+}
+
+impl FindingsCollector {
+    pub fn push(&self, finding: AuditFinding) {
+        self.inner.lock().unwrap().push(finding);
+    }
+
+    pub fn extend(&self, findings: impl IntoIterator<Item = AuditFinding>) {
+        self.inner.lock().unwrap().extend(findings);
+    }
+
+    pub fn into_inner(self) -> Vec<AuditFinding> {
+        self.inner.into_inner().unwrap()
+    }
+}
+
+fn timed(phase: impl FnOnce()) -> u128 {
+    let started = Instant::now();
+    phase();
+    started.elapsed().as_millis()
+}
+
+/// Разход на одита: стенно време по фази и обем на сканирането.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AuditStats {
+    pub files_scanned: usize,
+    pub registry_ms: u128,
+    pub redundancy_ms: u128,
+    pub dead_code_ms: u128,
+    pub logic_gaps_ms: u128,
+    pub total_ms: u128,
+    /// Нишки в rayon пула; `total_ms * worker_threads` е горна граница на CPU цената.
+    pub worker_threads: usize,
+}
+
 pub struct SovereignAudit {
-    pub symbol_registry: DashMap<String, SymbolInfo>,
+    /// Всички символи с дадено име; еднаквите имена не се презаписват,
+    /// за да може проходът за излишъци да сравни `body_hash`-овете им.
+    pub symbol_registry: DashMap<String, Vec<SymbolInfo>>,
     pub findings: Vec<AuditFinding>,
 }
 
@@ -30,6 +119,10 @@ pub struct SymbolInfo {
     pub file_path: PathBuf,
     pub line: usize,
     pub hash: String,
+    /// Хеш на всичко след името (сигнатура + тяло): еднакви имена с различна
+    /// логика се различават тук, а истинските дубликати съвпадат.
+    #[serde(default)]
+    pub body_hash: String,
 }
 
 impl SovereignAudit {
@@ -41,74 +134,143 @@ impl SovereignAudit {
     }
 
     /// ФАЗА 1-6: Екзекуция на Пълния Одит
-    pub async fn run_full_audit(&mut self, projects: Vec<PathBuf>) -> SovereignResult<()> {
-        println!("🏛️  SOVEREIGN AUDIT: INITIATING EMPIRE SCAN...");
-        
-        // Phase 1: Build Symbol Registry (Parallel)
-        self.build_registry(&projects)?;
-
-        // Phase 2: Redundancy Detection
-        self.detect_redundancy();
+    pub async fn run_full_audit(&mut self, projects: Vec<PathBuf>) -> SovereignResult<AuditStats> {
+        crate::sovereign_log!(info, "🏛️  SOVEREIGN AUDIT: INITIATING EMPIRE SCAN...");
+        let mut stats = AuditStats {
+            worker_threads: rayon::current_num_threads(),
+            ..Default::default()
+        };
+        let started = Instant::now();
 
-        // Phase 3: Dead Code Analysis
-        self.detect_dead_code();
+        // Phase 1: Build Symbol Registry (Parallel)
+        let phase = Instant::now();
+        stats.files_scanned = self.build_registry(&projects)?;
+        stats.registry_ms = phase.elapsed().as_millis();
 
-        // Phase 4: Logic Gap Detection (Regex Engine)
-        self.detect_logic_gaps(&projects);
+        // Phase 2-4: Redundancy, Dead Code и Logic Gaps (Regex Engine) вървят паралелно
+        // и събират находките в общ колектор
+        let sink = FindingsCollector::default();
+        let ((redundancy_ms, dead_code_ms), logic_gaps_ms) = rayon::join(
+            || {
+                rayon::join(
+                    || timed(|| self.detect_redundancy(&sink)),
+                    || timed(|| self.detect_dead_code(&sink)),
+                )
+            },
+            || timed(|| self.detect_logic_gaps(&projects, &sink)),
+        );
+        stats.redundancy_ms = redundancy_ms;
+        stats.dead_code_ms = dead_code_ms;
+        stats.logic_gaps_ms = logic_gaps_ms;
+        self.findings.extend(sink.into_inner());
 
-        println!("✅ AUDIT COMPLETE. ENTROPY MAPPED.");
-        Ok(())
+        stats.total_ms = started.elapsed().as_millis();
+        crate::sovereign_log!(
+            info,
+            "✅ AUDIT COMPLETE. ENTROPY MAPPED. {} FILES IN {}ms (registry {}ms, redundancy {}ms, dead code {}ms, logic gaps {}ms)",
+            stats.files_scanned,
+            stats.total_ms,
+            stats.registry_ms,
+            stats.redundancy_ms,
+            stats.dead_code_ms,
+            stats.logic_gaps_ms
+        );
+        Ok(stats)
     }
 
-    fn build_registry(&self, paths: &[PathBuf]) -> SovereignResult<()> {
+    fn build_registry(&self, paths: &[PathBuf]) -> SovereignResult<usize> {
+        let files_scanned = AtomicUsize::new(0);
         paths.par_iter().for_each(|path| {
             let walker = WalkBuilder::new(path)
                 .standard_filters(true)
                 .build();
 
             for entry in walker.flatten() {
-                if entry.file_type().map_or(false, |ft| ft.is_file()) {
-                    if let Some(ext) = entry.path().extension() {
-                        if ext == "rs" || ext == "ts" || ext == "js" {
-                            self.index_file(entry.path());
-                        }
-                    }
+                if entry.file_type().map_or(false, |ft| ft.is_file()) && Self::is_indexable(entry.path()) {
+                    self.index_file(entry.path());
+                    files_scanned.fetch_add(1, Ordering::Relaxed);
                 }
             }
         });
-        Ok(())
+        Ok(files_scanned.into_inner())
+    }
+
+    /// ПОТОЧЕН ИНДЕКС: Обхожда файловете един по един и подава всеки символ на `on_symbol`,
+    /// без да пълни `symbol_registry`. Връща броя на подадените символи.
+    pub fn ingest_streaming<F>(path: &Path, mut on_symbol: F) -> SovereignResult<usize>
+    where
+        F: FnMut(SymbolInfo),
+    {
+        let walker = WalkBuilder::new(path)
+            .standard_filters(true)
+            .build();
+
+        let mut emitted = 0;
+        for entry in walker.flatten() {
+            if entry.file_type().map_or(false, |ft| ft.is_file()) && Self::is_indexable(entry.path()) {
+                for info in Self::extract_symbols(entry.path()) {
+                    on_symbol(info);
+                    emitted += 1;
+                }
+            }
+        }
+        Ok(emitted)
+    }
+
+    fn is_indexable(path: &Path) -> bool {
+        path.extension().map_or(false, |ext| ext == "rs" || ext == "ts" || ext == "js")
     }
 
     fn index_file(&self, path: &Path) {
+        for info in Self::extract_symbols(path) {
+            let mut same_name = self.symbol_registry.entry(info.name.clone()).or_default();
+            // Повторно индексиране на файла заменя символа, вместо да го дублира.
+            match same_name
+                .iter_mut()
+                .find(|s| s.file_path == info.file_path && s.line == info.line)
+            {
+                Some(existing) => *existing = info,
+                None => same_name.push(info),
+            }
+        }
+    }
+
+    fn extract_symbols(path: &Path) -> Vec<SymbolInfo> {
+        let mut symbols = Vec::new();
         if let Ok(file) = fs::File::open(path) {
             if let Ok(mmap) = unsafe { Mmap::map(&file) } {
                 let content = String::from_utf8_lossy(&mmap);
+                let starts = line_starts(&content);
                 
                 // Rust/TS Symbol Extraction Logic
                 if let Ok(re) = Regex::new(r"(export\s+)?(class|fn|function|struct|enum|interface)\s+([a-zA-Z_][a-zA-Z0-9_]*)") {
                     for cap in re.captures_iter(&content) {
                         let name = cap[3].to_string();
-                        let info = SymbolInfo {
-                            name: name.clone(),
+                        let offset = cap.get(0).map_or(0, |m| m.start());
+                        let body_start = cap.get(3).map_or(offset, |m| m.end());
+                        let body = &content[body_start..body_end(&content, body_start)];
+                        symbols.push(SymbolInfo {
+                            hash: format!("{:x}", md5::compute(name.as_bytes())),
+                            name,
                             project: "Empire".into(),
                             file_path: path.to_path_buf(),
-                            line: 0, 
-                            hash: format!("{:x}", md5::compute(name.as_bytes())),
-                        };
-                        self.symbol_registry.insert(name, info);
+                            line: line_of(&starts, offset),
+                            body_hash: body_hash(body),
+                        });
                     }
                 }
             }
         }
+        symbols
     }
 
-    fn detect_logic_gaps(&mut self, paths: &[PathBuf]) {
+    fn detect_logic_gaps(&self, paths: &[PathBuf], sink: &FindingsCollector) {
         let patterns = vec![
             (Regex::new(r"TODO:|FIXME:").unwrap(), FindingType::LogicGap, "Technical Debt Found"),
             (Regex::new(r"\bany\b").unwrap(), FindingType::Security, "Unsafe 'any' type detected"),
         ];
 
-        let findings: Vec<AuditFinding> = paths.par_iter().flat_map(|path| {
+        paths.par_iter().for_each(|path| {
             let walker = WalkBuilder::new(path)
                 .standard_filters(true)
                 .build();
@@ -120,15 +282,20 @@ impl SovereignAudit {
                     if let Ok(file) = fs::File::open(entry.path()) {
                         if let Ok(mmap) = unsafe { Mmap::map(&file) } {
                             let content = String::from_utf8_lossy(&mmap);
+                            let starts = line_starts(&content);
                             
                             for (re, f_type, title) in &patterns {
-                                if re.is_match(&content) {
+                                let mut lines: Vec<usize> =
+                                    re.find_iter(&content).map(|m| line_of(&starts, m.start())).collect();
+                                lines.dedup();
+                                if let Some(&first) = lines.first() {
                                     local_findings.push(AuditFinding {
                                         id: Uuid::new_v4().to_string(),
                                         f_type: f_type.clone(),
                                         title: title.to_string(),
                                         files: vec![entry.path().to_path_buf()],
-                                        impact_lines: 1, // Simplified
+                                        impact_lines: lines.len(),
+                                        line: Some(first),
                                         suggestion: "Review and entrench stable logic.".into(),
                                     });
                                 }
@@ -137,12 +304,131 @@ impl SovereignAudit {
                     }
                 }
             }
-            local_findings
-        }).collect();
+            sink.extend(local_findings);
+        });
+    }
+
+    fn detect_redundancy(&self, _sink: &FindingsCollector) { }
+    fn detect_dead_code(&self, _sink: &FindingsCollector) { }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("lwas_audit_{}_{}", name, Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_stats_count_indexed_files() {
+        let dir = fixture_dir("stats");
+        fs::write(dir.join("a.rs"), "fn alpha() {}\n").unwrap();
+        fs::write(dir.join("b.ts"), "function beta() {}\n").unwrap();
+        fs::write(dir.join("notes.txt"), "fn ignored() {}\n").unwrap();
+
+        let mut audit = SovereignAudit::new();
+        let stats = audit.run_full_audit(vec![dir.clone()]).await.unwrap();
+
+        assert_eq!(stats.files_scanned, 2);
+        assert!(stats.total_ms >= stats.registry_ms);
+        let _ = fs::remove_dir_all(dir);
+    }
 
-        self.findings.extend(findings);
+    #[test]
+    fn test_symbols_and_gaps_carry_line_numbers() {
+        let dir = fixture_dir("lines");
+        let file = dir.join("lines.rs");
+        fs::write(&file, "// header\n\nuse std::fs;\n\nfn foo() {}\n// TODO: split\n// TODO: rename\n").unwrap();
+
+        let audit = SovereignAudit::new();
+        audit.index_file(&file);
+        assert_eq!(audit.symbol_registry.get("foo").unwrap()[0].line, 5);
+
+        let sink = FindingsCollector::default();
+        audit.detect_logic_gaps(&[dir.clone()], &sink);
+        let findings = sink.into_inner();
+        let gap = findings.iter().find(|f| f.f_type == FindingType::LogicGap).unwrap();
+        assert_eq!((gap.line, gap.impact_lines), (Some(6), 2));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_body_hash_separates_name_collisions_from_duplicates() {
+        let dir = fixture_dir("bodies");
+        let file = dir.join("dup.rs");
+        fs::write(
+            &file,
+            "fn load() { read_disk(); }\nfn load() { fetch_net(); }\nfn twin() {\n    read_disk();\n}\n",
+        )
+        .unwrap();
+
+        let symbols = SovereignAudit::extract_symbols(&file);
+        let (first, second, twin) = (&symbols[0], &symbols[1], &symbols[2]);
+
+        assert_eq!(first.hash, second.hash);
+        assert_ne!(first.body_hash, second.body_hash);
+        assert_eq!(first.body_hash, twin.body_hash);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_array_signatures_hash_the_whole_body_and_keep_both_symbols() {
+        let dir = fixture_dir("arrays");
+        let file = dir.join("keys.rs");
+        fs::write(
+            &file,
+            "fn f(k: [u8; 32]) { read_disk(); }\nfn f(k: [u8; 32]) { fetch_net(); }\n",
+        )
+        .unwrap();
+
+        let audit = SovereignAudit::new();
+        audit.index_file(&file);
+        audit.index_file(&file);
+
+        let same_name = audit.symbol_registry.get("f").unwrap();
+        assert_eq!(same_name.len(), 2);
+        assert_ne!(same_name[0].body_hash, same_name[1].body_hash);
+        let _ = fs::remove_dir_all(dir);
     }
 
-    fn detect_redundancy(&mut self) { }
-    fn detect_dead_code(&mut self) { }
+    #[test]
+    fn test_concurrent_passes_lose_no_findings() {
+        let left = fixture_dir("left");
+        let right = fixture_dir("right");
+        for i in 0..20 {
+            fs::write(left.join(format!("l{}.rs", i)), "// TODO: left\n").unwrap();
+            fs::write(right.join(format!("r{}.rs", i)), "// FIXME: right\n").unwrap();
+        }
+
+        let audit = SovereignAudit::new();
+        let sink = FindingsCollector::default();
+        std::thread::scope(|s| {
+            s.spawn(|| audit.detect_logic_gaps(&[left.clone()], &sink));
+            s.spawn(|| audit.detect_logic_gaps(&[right.clone()], &sink));
+        });
+
+        let findings = sink.into_inner();
+        assert_eq!(findings.len(), 40);
+        assert!(findings.iter().all(|f| f.f_type == FindingType::LogicGap));
+        let _ = fs::remove_dir_all(left);
+        let _ = fs::remove_dir_all(right);
+    }
+
+    #[test]
+    fn test_streaming_ingest_emits_every_symbol() {
+        let dir = fixture_dir("stream");
+        fs::write(dir.join("a.rs"), "struct Alpha;\nfn beta() {}\n").unwrap();
+        fs::write(dir.join("b.js"), "function gamma() {}\n").unwrap();
+
+        let mut seen = Vec::new();
+        let emitted = SovereignAudit::ingest_streaming(&dir, |info| seen.push(info.name)).unwrap();
+
+        seen.sort();
+        assert_eq!(emitted, 3);
+        assert_eq!(seen, vec!["Alpha", "beta", "gamma"]);
+        let _ = fs::remove_dir_all(dir);
+    }
 }
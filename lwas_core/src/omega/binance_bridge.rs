@@ -2,17 +2,30 @@
 // ARCHITECT: Dimitar Prodromov | AUTHORITY: AETERNA
 // STATUS: BINANCE_BRIDGE_ACTIVE // MODE: CAPITAL_EXTRACTION
 
-use crate::SovereignResult;
+use crate::omega::response_cache::ResponseCache;
+use crate::{SovereignError, SovereignResult};
 use hmac::{Hmac, Mac};
 use reqwest::header::{HeaderMap, HeaderValue};
 use serde_json::Value;
-use sha2::Sha256;
+use sha2::{Digest, Sha256};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+pub const PRODUCTION_BASE_URL: &str = "https://api.binance.com";
+/// Презаписва base URL-а, напр. https://testnet.binance.vision за тестнета.
+pub const BASE_URL_ENV: &str = "BINANCE_BASE_URL";
+pub const DEFAULT_TIME_OFFSET_MS: u64 = 1000;
+pub const DEFAULT_RECV_WINDOW_MS: u64 = 5000;
+
 pub struct BinanceBridge {
     api_key: String,
     secret_key: String,
     client: reqwest::Client,
+    /// Офлайн мостът не вика API-то и връща симулирани баланси.
+    offline: bool,
+    base_url: String,
+    /// С колко ms изоставаме от локалния часовник при подписване (компенсира clock skew).
+    time_offset_ms: u64,
+    recv_window_ms: u64,
 }
 
 impl BinanceBridge {
@@ -36,9 +49,84 @@ impl BinanceBridge {
             api_key,
             secret_key,
             client: reqwest::Client::new(),
+            offline: false,
+            base_url: Self::base_url_from_env(),
+            time_offset_ms: DEFAULT_TIME_OFFSET_MS,
+            recv_window_ms: DEFAULT_RECV_WINDOW_MS,
         })
     }
 
+    /// Мост без ключове: не изисква env променливи и не излиза в мрежата.
+    pub fn offline() -> Self {
+        Self {
+            api_key: String::new(),
+            secret_key: String::new(),
+            client: reqwest::Client::new(),
+            offline: true,
+            base_url: Self::base_url_from_env(),
+            time_offset_ms: DEFAULT_TIME_OFFSET_MS,
+            recv_window_ms: DEFAULT_RECV_WINDOW_MS,
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_timing(mut self, time_offset_ms: u64, recv_window_ms: u64) -> Self {
+        self.time_offset_ms = time_offset_ms;
+        self.recv_window_ms = recv_window_ms;
+        self
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn base_url_from_env() -> String {
+        std::env::var(BASE_URL_ENV)
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|_| PRODUCTION_BASE_URL.to_string())
+    }
+
+    fn timestamp(&self) -> SovereignResult<u128> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| SovereignError::LogicCollapse(format!("CLOCK: {}", e)))?;
+        Ok(now.as_millis().saturating_sub(self.time_offset_ms as u128))
+    }
+
+    fn timed_query(&self, timestamp: u128) -> String {
+        format!("timestamp={}&recvWindow={}", timestamp, self.recv_window_ms)
+    }
+
+    /// `<base_url><path>?<query>&signature=<HMAC>`
+    fn signed_url(&self, path: &str, query: &str) -> String {
+        format!("{}{}?{}&signature={}", self.base_url, path, query, self.sign(query))
+    }
+
+    /// Кеш ключ за подписан endpoint: base URL + отпечатък на API ключа,
+    /// за да не вижда един мост балансите на друг (или на тестнета).
+    fn cache_key(&self, path: &str) -> (String, String) {
+        let fingerprint = hex::encode(&Sha256::digest(self.api_key.as_bytes())[..8]);
+        (
+            format!("binance:{}{}", self.base_url, path),
+            format!("key={}", fingerprint),
+        )
+    }
+
+    fn simulated_balances() -> Vec<Value> {
+        vec![
+            serde_json::json!({ "asset": "USDC", "free": "1000.00", "locked": "0.00", "simulated": true }),
+            serde_json::json!({ "asset": "SOL", "free": "4.20", "locked": "0.00", "simulated": true }),
+        ]
+    }
+
     fn sign(&self, payload: &str) -> String {
         let mut mac = Hmac::<Sha256>::new_from_slice(self.secret_key.as_bytes())
             .expect("HMAC can take key of any size");
@@ -47,24 +135,31 @@ impl BinanceBridge {
     }
 
     pub async fn get_account_balance(&self) -> SovereignResult<Vec<Value>> {
-        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() - 1000;
-        let query = format!("timestamp={}&recvWindow=5000", timestamp);
-        let signature = self.sign(&query);
-        let url = format!(
-            "https://api.binance.com/api/v3/account?{}&signature={}",
-            query, signature
-        );
+        if self.offline || crate::mode::is_simulation() {
+            return Ok(Self::simulated_balances());
+        }
+
+        let timestamp = self.timestamp()?;
+        let url = self.signed_url("/api/v3/account", &self.timed_query(timestamp));
 
         let mut headers = HeaderMap::new();
         headers.insert("X-MBX-APIKEY", HeaderValue::from_str(&self.api_key)?);
 
-        let resp = self
-            .client
-            .get(url)
-            .headers(headers.clone())
-            .send()
-            .await?
-            .json::<Value>()
+        // Подписаната заявка се сменя с всеки timestamp, затова не влиза в ключа.
+        // Кешират се само 2xx отговори - грешка от API-то не бива да се помни 30s.
+        let (endpoint, params) = self.cache_key("/api/v3/account");
+        let resp = ResponseCache::shared()
+            .get_or_fetch(&endpoint, &params, || async {
+                Ok(self
+                    .client
+                    .get(url)
+                    .headers(headers.clone())
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<Value>()
+                    .await?)
+            })
             .await?;
 
         let mut all_assets = Vec::new();
@@ -90,13 +185,8 @@ impl BinanceBridge {
         }
 
         // ПРОВЕРКА НА FUNDING WALLET (Често там отиват парите от директна покупка)
-        let funding_url = "https://api.binance.com/sapi/v1/asset/get-funding-asset";
-        let funding_query = format!("timestamp={}&recvWindow=5000", timestamp);
-        let funding_signature = self.sign(&funding_query);
-        let funding_full_url = format!(
-            "{}?{}&signature={}",
-            funding_url, funding_query, funding_signature
-        );
+        let funding_full_url =
+            self.signed_url("/sapi/v1/asset/get-funding-asset", &self.timed_query(timestamp));
 
         if let Ok(f_resp) = self
             .client
@@ -139,19 +229,66 @@ impl BinanceBridge {
             side, symbol, quantity
         );
 
-        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() - 1000;
-        let query = format!(
-            "symbol={}&side={}&type=MARKET&quantity={}&timestamp={}",
-            symbol, side, quantity, timestamp
-        );
-        let signature = self.sign(&query);
-
-        let url = "https://api.binance.com/api/v3/order";
-        let mut headers = HeaderMap::new();
-        headers.insert("X-MBX-APIKEY", HeaderValue::from_str(&self.api_key)?);
+        if self.offline || crate::mode::is_simulation() {
+            println!("🧪 [SIMULATED]: Офлайн режим. Поръчката не е изпратена.");
+            return Ok(());
+        }
 
         println!("✨ [TX_SENT]: Binance Order Manifested. Logic confirmed.");
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_offline_bridge_needs_no_credentials() {
+        std::env::remove_var("BINANCE_API_KEY");
+        std::env::remove_var("BINANCE_SECRET_KEY");
+
+        let bridge = BinanceBridge::offline();
+        let balances = bridge.get_account_balance().await.unwrap();
+
+        assert!(bridge.is_offline());
+        assert!(!balances.is_empty());
+        assert!(balances.iter().all(|b| b["simulated"] == true));
+    }
+
+    #[test]
+    fn test_account_cache_key_is_scoped_to_base_url_and_api_key() {
+        let mut a = BinanceBridge::offline().with_base_url("https://api.binance.com");
+        a.api_key = "KEY-A".into();
+        let mut b = BinanceBridge::offline().with_base_url("https://api.binance.com");
+        b.api_key = "KEY-B".into();
+        let testnet = BinanceBridge::offline().with_base_url("https://testnet.binance.vision");
+
+        let (endpoint_a, params_a) = a.cache_key("/api/v3/account");
+        let (endpoint_b, params_b) = b.cache_key("/api/v3/account");
+
+        assert_eq!(endpoint_a, endpoint_b);
+        assert_ne!(params_a, params_b);
+        assert!(!params_a.contains("KEY-A"));
+        assert_ne!(testnet.cache_key("/api/v3/account").0, endpoint_a);
+    }
+
+    #[test]
+    fn test_signed_urls_use_configured_base() {
+        let bridge = BinanceBridge::offline()
+            .with_base_url("http://mock.binance.local/")
+            .with_timing(250, 10_000);
+        let query = bridge.timed_query(1_700_000_000_000);
+
+        assert_eq!(query, "timestamp=1700000000000&recvWindow=10000");
+        assert_eq!(
+            bridge.signed_url("/api/v3/account", &query),
+            format!(
+                "http://mock.binance.local/api/v3/account?{}&signature={}",
+                query,
+                bridge.sign(&query)
+            )
+        );
+    }
+}
@@ -17,17 +17,21 @@ pub struct BrainResponse {
 
 pub struct SovereignBrainAPI;
 
+pub const DEFAULT_BRAIN_ADDR: ([u8; 4], u16) = ([127, 0, 0, 1], 9999);
+
 impl SovereignBrainAPI {
-    pub async fn start(vsh: Arc<VectorSpaceHeap>) -> SovereignResult<()> {
+    pub fn default_addr() -> SocketAddr {
+        SocketAddr::from(DEFAULT_BRAIN_ADDR)
+    }
+
+    pub async fn start(vsh: Arc<VectorSpaceHeap>, addr: SocketAddr) -> SovereignResult<()> {
         let app = Router::new()
             .route("/execute", post(process_command))
             .with_state(vsh);
 
-        let addr = SocketAddr::from(([127, 0, 0, 1], 9999));
         println!("🧠 SOVEREIGN BRAIN API ONLINE AT http://{}", addr);
 
-        let listener = TcpListener::bind(addr).await
-            .map_err(|e| SovereignError::IoError(e.to_string()))?;
+        let listener = TcpListener::bind(addr).await?;
         
         axum::serve(listener, app).await
             .map_err(|e| SovereignError::LogicCollapse(e.to_string()))?;
@@ -67,3 +71,30 @@ impl SovereignInferenceEngine {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_brain_binds_provided_addr() {
+        let probe = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = probe.local_addr().unwrap();
+        drop(probe);
+
+        let vsh = Arc::new(VectorSpaceHeap::new().unwrap());
+        let server = tokio::spawn(SovereignBrainAPI::start(vsh, addr));
+
+        let mut connected = false;
+        for _ in 0..50 {
+            if tokio::net::TcpStream::connect(addr).await.is_ok() {
+                connected = true;
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        server.abort();
+        assert!(connected);
+        assert_eq!(SovereignBrainAPI::default_addr().port(), 9999);
+    }
+}
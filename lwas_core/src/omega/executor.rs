@@ -2,7 +2,7 @@
 // ARCHITECT: Dimitar Prodromov | AUTHORITY: AETERNA
 // STATUS: PHYSICAL_EXECUTION_LEVEL_10 // MODE: LIVE_TRANSACTION
 
-use solana_client::rpc_client::RpcClient;
+use crate::omega::solana_rpc::SolanaRpc;
 use solana_sdk::{
     signature::{Keypair, Signer},
     system_instruction,
@@ -23,8 +23,8 @@ pub struct SwapQuote {
 
 impl ExecutionEngine {
     /// ВЕЧЕ НЕМА СИМУЛАЦИИ. ПЪЛНА МАНИФЕСТАЦИЯ.
-    pub async fn execute_atomic_swap(
-        client: &RpcClient,
+    pub async fn execute_atomic_swap<R: SolanaRpc>(
+        client: &R,
         keypair: &Keypair,
         _quote: SwapQuote,
     ) -> SovereignResult<()> {
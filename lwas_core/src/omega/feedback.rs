@@ -1,14 +1,39 @@
 use crate::prelude::*;
+use tokio::sync::watch;
 use tokio::time::{sleep, Duration};
 
 pub struct FeedbackLoop;
 
+const BASE_INTERVAL: Duration = Duration::from_secs(10);
+const MAX_INTERVAL: Duration = Duration::from_secs(160);
+/// Резонансът, който остава на всяка точка след един цикъл.
+const RESONANCE_DECAY: f64 = 0.98;
+
+/// Без промяна цикълът се отдръпва (двоен интервал до MAX); всяка промяна го връща към BASE.
+fn next_interval(current: Duration, delta: &VshDelta) -> Duration {
+    if delta.is_zero() {
+        (current * 2).min(MAX_INTERVAL)
+    } else {
+        BASE_INTERVAL
+    }
+}
+
 impl FeedbackLoop {
-    pub async fn run_evolution_cycle(_vsh: Arc<VectorSpaceHeap>) {
+    pub async fn run_evolution_cycle(vsh: Arc<VectorSpaceHeap>, mut shutdown: watch::Receiver<bool>) {
         println!("🧬 NEURAL FEEDBACK LOOP: ONLINE. MONITORING ENTROPY...");
-        
+
+        let mut prev = vsh.get_state();
+        let mut interval = BASE_INTERVAL;
         loop {
-            sleep(Duration::from_secs(10)).await;
+            tokio::select! {
+                _ = sleep(interval) => {}
+                _ = shutdown.wait_for(|stop| *stop) => break,
+            }
+            vsh.decay_resonance(RESONANCE_DECAY);
+            let state = vsh.get_state();
+            interval = next_interval(interval, &state.delta(&prev));
+            prev = state;
         }
+        println!("🧬 NEURAL FEEDBACK LOOP: OFFLINE.");
     }
 }
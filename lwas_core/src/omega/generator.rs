@@ -17,8 +17,13 @@ pub struct SovereignGenerator {
 
 impl SovereignGenerator {
     pub fn new() -> Self {
+        Self::in_workspace(Path::new("."))
+    }
+
+    /// Генератор, чийто трезор за активи лежи под `root`.
+    pub fn in_workspace(root: &Path) -> Self {
         Self { 
-            asset_vault: PathBuf::from("./assets/sovereign_saas"),
+            asset_vault: root.join("assets/sovereign_saas"),
             market_threshold: 0.85, 
         }
     }
@@ -30,6 +35,7 @@ impl SovereignGenerator {
              title: cluster_name.to_string(),
              files: files.clone(),
              impact_lines: 100,
+             line: None,
              f_type: FindingType::Redundancy,
              suggestion: "Autonomous extraction".to_string(),
          };
@@ -52,19 +58,19 @@ impl SovereignGenerator {
              return Err(SovereignError::VshError("Node Not Found".into()));
         }
 
-        let logic_payload = fs::read_to_string(&gem.files[0]).map_err(|e| SovereignError::IoError(e.to_string()))?;
+        let logic_payload = fs::read_to_string(&gem.files[0])?;
         let saas_code = self.wrap_in_sovereign_api(&logic_payload);
 
         let asset_id = format!("MM_SAAS_{}", Uuid::new_v4().simple());
         let asset_path = self.asset_vault.join(&asset_id);
         
         if !asset_path.exists() {
-             fs::create_dir_all(&asset_path).map_err(|e| SovereignError::IoError(e.to_string()))?;
+             fs::create_dir_all(&asset_path)?;
         }
         
         let src_path = asset_path.join("src");
-        fs::create_dir_all(&src_path).map_err(|e| SovereignError::IoError(e.to_string()))?;
-        fs::write(src_path.join("main.rs"), saas_code).map_err(|e| SovereignError::IoError(e.to_string()))?;
+        fs::create_dir_all(&src_path)?;
+        fs::write(src_path.join("main.rs"), saas_code)?;
         
         self.generate_manifest(&asset_path, &asset_id)?;
 
@@ -73,7 +79,7 @@ impl SovereignGenerator {
         vsh.allocate(
             format!("MM_SAAS:{}", gem.title),
             vec![1.0; 128], 
-        );
+        )?;
 
         println!("✨ ASSET GENERATED: {} | ESTIMATED EQUITY: ${:.2}", asset_id, asset_value);
 
@@ -117,7 +123,7 @@ tokio = {{ version = "1", features = ["full"] }}
 "#,
             name.to_lowercase()
         );
-        fs::write(path.join("Cargo.toml"), toml).map_err(|e| SovereignError::IoError(e.to_string()))?;
+        fs::write(path.join("Cargo.toml"), toml)?;
         Ok(())
     }
 }
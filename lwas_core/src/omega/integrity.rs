@@ -1,7 +1,11 @@
 // lwas_core/src/omega/integrity.rs
 use crate::prelude::*;
+use sha2::{Digest, Sha256};
 use std::fs;
-use std::io;
+
+/// Семе за Logos хеша; пиърите трябва да ползват същото, за да го проверят.
+pub const LOGOS_SEED_ENV: &str = "AETERNA_LOGOS_SEED";
+pub const DEFAULT_LOGOS_SEED: &str = "QANTUM_JULES_DIAMOND";
 
 pub struct VoidWatcher;
 
@@ -9,10 +13,8 @@ impl VoidWatcher {
     pub fn scan_for_entropy(root_path: &str) -> SovereignResult<()> {
         let forbidden = ["node_modules", "target/debug"];
 
-        for entry in fs::read_dir(root_path)
-            .map_err(|e: io::Error| SovereignError::IoError(e.to_string()))?
-        {
-            let entry = entry.map_err(|e: io::Error| SovereignError::IoError(e.to_string()))?;
+        for entry in fs::read_dir(root_path)? {
+            let entry = entry?;
             let path = entry.path();
 
             if path.is_dir() {
@@ -28,7 +30,60 @@ impl VoidWatcher {
         Ok(())
     }
 
-    pub fn generate_logos_hash() -> String {
-        "0xQANTUM_JULES_DIAMOND_STRICT_VAL".to_string()
+    /// Diamond сигналът: детерминистичен SHA-256 от версията на crate-а,
+    /// семето от `AETERNA_LOGOS_SEED` и хеша на състоянието на VSH.
+    pub fn generate_logos_hash(state: &VshState) -> String {
+        Self::generate_logos_hash_with(&Self::logos_seed(), state)
+    }
+
+    pub fn generate_logos_hash_with(seed: &str, state: &VshState) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.update(seed.as_bytes());
+        hasher.update(Self::state_hash(state));
+        format!("0x{}", hex::encode(hasher.finalize()))
+    }
+
+    /// Пиърите преизчисляват хеша от същото състояние и сравняват.
+    pub fn verify_logos_hash(hash: &str, state: &VshState) -> bool {
+        Self::generate_logos_hash(state) == hash
+    }
+
+    fn logos_seed() -> String {
+        std::env::var(LOGOS_SEED_ENV).unwrap_or_else(|_| DEFAULT_LOGOS_SEED.to_string())
+    }
+
+    fn state_hash(state: &VshState) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update((state.total_points as u64).to_le_bytes());
+        hasher.update(state.entropy.to_bits().to_le_bytes());
+        hasher.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_logos_hash_is_stable_and_tracks_state() {
+        let state = VshState { total_points: 42, entropy: 0.25 };
+        let hash = VoidWatcher::generate_logos_hash_with("SEED", &state);
+
+        let grown = VshState { total_points: 43, entropy: 0.25 };
+
+        assert_eq!(hash, VoidWatcher::generate_logos_hash_with("SEED", &state.clone()));
+        assert_ne!(hash, VoidWatcher::generate_logos_hash_with("SEED", &grown));
+        assert_ne!(hash, VoidWatcher::generate_logos_hash_with("OTHER", &state));
+    }
+
+    #[test]
+    fn test_verify_accepts_own_hash_only() {
+        let state = VshState { total_points: 7, entropy: 0.5 };
+        let hash = VoidWatcher::generate_logos_hash(&state);
+
+        assert!(VoidWatcher::verify_logos_hash(&hash, &state));
+        let drifted = VshState { total_points: 8, entropy: 0.5 };
+        assert!(!VoidWatcher::verify_logos_hash(&hash, &drifted));
     }
 }
@@ -25,10 +25,12 @@ pub mod observer;
 pub mod onto;
 pub mod ontological_bridge;
 pub mod oracle;
+pub mod response_cache;
 pub mod rl;
 pub mod scribe;
 pub mod server;
 pub mod simulation;
+pub mod solana_rpc;
 pub mod soul_compiler;
 pub mod soul_engine;
 pub mod sovereign_command;
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use std::path::Path;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum AxiomType {
@@ -29,6 +30,36 @@ pub struct Axiom {
     pub coherence_score: f64,
 }
 
+/// Аксиома, която трябва да се манифестира при синтез на реалност.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AxiomSeed {
+    pub expression: String,
+    pub f_type: AxiomType,
+}
+
+impl AxiomSeed {
+    pub fn new(expression: &str, f_type: AxiomType) -> Self {
+        Self {
+            expression: expression.to_string(),
+            f_type,
+        }
+    }
+}
+
+/// Аксиомите по подразбиране: само съществуването (∃x: x = x).
+pub fn default_axioms() -> Vec<AxiomSeed> {
+    vec![AxiomSeed::new("∃x: x = x", AxiomType::Ontological)]
+}
+
+/// Зарежда набор от аксиоми от JSON конфигурация (масив от `AxiomSeed`).
+/// Невалиден JSON е `LogicCollapse`, а не I/O грешка.
+pub fn load_axiom_seeds(path: &Path) -> SovereignResult<Vec<AxiomSeed>> {
+    let raw = std::fs::read_to_string(path)?;
+    serde_json::from_str(&raw).map_err(|e| {
+        SovereignError::LogicCollapse(format!("AXIOM_SEEDS {}: {}", path.display(), e))
+    })
+}
+
 pub struct SovereignOntoEngine {
     pub axioms: Arc<DashMap<Uuid, Axiom>>,
     pub reality_matrix: Arc<VectorSpaceHeap>,
@@ -57,7 +88,7 @@ impl SovereignOntoEngine {
         // Математическо втвърдяване (Entrenchment) в VSH
         let vector = self.project_expression_to_vector(expression);
         self.reality_matrix
-            .allocate(format!("AXIOM:{}", expression), vector);
+            .allocate(format!("AXIOM:{}", expression), vector)?;
 
         println!(
             "⚖️ ONTO-ENGINE: AXIOM MANIFESTED: {} ({:?})",
@@ -68,16 +99,27 @@ impl SovereignOntoEngine {
 
     /// СИНТЕЗ: Създава нова логическа реалност в VSH
     pub fn synthesize_reality(&self, name: &str) -> SovereignResult<()> {
+        self.synthesize_reality_with(name, &default_axioms()).map(|_| ())
+    }
+
+    /// СИНТЕЗ с избран набор аксиоми. Връща id-тата на манифестираните аксиоми.
+    pub fn synthesize_reality_with(
+        &self,
+        name: &str,
+        axioms: &[AxiomSeed],
+    ) -> SovereignResult<Vec<Uuid>> {
         println!("🌀 ONTO-ENGINE: SYNTHESIZING REALITY '{}'...", name);
 
-        // Инстанциране на Онтологична Аксиома (Existence)
-        let _ = self.manifest_axiom("∃x: x = x", AxiomType::Ontological)?;
+        let ids = axioms
+            .iter()
+            .map(|seed| self.manifest_axiom(&seed.expression, seed.f_type.clone()))
+            .collect::<SovereignResult<Vec<_>>>()?;
 
-        // Мапване на Аксиомата към 2-та милиарда точки
+        // Мапване на Аксиомите към 2-та милиарда точки
         self.reality_matrix
-            .allocate(format!("REALITY_ROOT:{}", name), vec![1.0; 128]);
+            .allocate(format!("REALITY_ROOT:{}", name), vec![1.0; 128])?;
 
-        Ok(())
+        Ok(ids)
     }
 
     fn project_expression_to_vector(&self, expr: &str) -> Vec<f32> {
@@ -89,3 +131,53 @@ impl SovereignOntoEngine {
         v
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reality_manifests_custom_axioms() {
+        let vsh = Arc::new(VectorSpaceHeap::new().unwrap());
+        let engine = SovereignOntoEngine::new(Arc::clone(&vsh));
+        let axioms = vec![
+            AxiomSeed::new("A -> B", AxiomType::Logical),
+            AxiomSeed::new("t0 < t1", AxiomType::Temporal),
+        ];
+
+        let ids = engine.synthesize_reality_with("MIRROR", &axioms).unwrap();
+
+        assert_eq!(ids.len(), 2);
+        assert_eq!(engine.axioms.len(), 2);
+        for seed in &axioms {
+            let label = format!("AXIOM:{}", seed.expression);
+            assert!(vsh.points.iter().any(|p| p.metadata == label), "{} missing", label);
+        }
+        assert!(vsh.points.iter().any(|p| p.metadata == "REALITY_ROOT:MIRROR"));
+    }
+
+    #[test]
+    fn test_default_reality_keeps_existence_axiom() {
+        let vsh = Arc::new(VectorSpaceHeap::new().unwrap());
+        let engine = SovereignOntoEngine::new(Arc::clone(&vsh));
+
+        engine.synthesize_reality("PRIME").unwrap();
+
+        assert!(vsh.points.iter().any(|p| p.metadata == "AXIOM:∃x: x = x"));
+    }
+
+    #[test]
+    fn test_axiom_seed_errors_keep_their_kind() {
+        let dir = std::env::temp_dir().join(format!("lwas_axioms_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let malformed = dir.join("axioms.json");
+        std::fs::write(&malformed, "[{\"expression\": 42}]").unwrap();
+
+        assert!(matches!(load_axiom_seeds(&malformed), Err(SovereignError::LogicCollapse(_))));
+        match load_axiom_seeds(&dir.join("missing.json")) {
+            Err(SovereignError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+            other => panic!("expected Io(NotFound), got {:?}", other),
+        }
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
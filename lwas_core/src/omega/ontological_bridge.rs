@@ -3,57 +3,209 @@
 // STATUS: BRIDGE_STABILIZED // MODE: ONTOLOGICAL_OPERATING_SYSTEM
 
 use crate::omega::soul_compiler::SoulCompiler;
-use aeterna_node::vm::interpreter::VirtualMachine;
+use aeterna_node::vm::bytecode::AeternaOpcode;
+use aeterna_node::vm::interpreter::{VirtualMachine, VmError};
+use hmac::{Hmac, Mac};
 use lwas_parser::parse_soul;
+use serde::Serialize;
+use sha2::Sha256;
+
+/// HMAC ключът, с който се подписват `.soul` файловете.
+pub const SOUL_KEY_ENV: &str = "AETERNA_SOUL_KEY";
+
+/// Инструкции, които една команда от моста може да изпълни, преди да се откаже.
+pub const BRIDGE_STEP_BUDGET: usize = 100_000;
+/// Стойности, които PRINT може да изведе в една команда.
+pub const BRIDGE_OUTPUT_CAP: usize = 1_024;
+
+/// Hex HMAC-SHA256 над съдържанието - това, което се пише в `<файл>.soul.sig`.
+pub fn sign_soul(content: &[u8], key: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(content);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Отговорът на моста към Tauri: дали командата е минала и съобщението за терминала.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BridgeResult {
+    pub success: bool,
+    pub message: String,
+}
+
+impl BridgeResult {
+    fn ok(message: impl Into<String>) -> Self {
+        Self { success: true, message: message.into() }
+    }
+
+    fn fail(message: impl Into<String>) -> Self {
+        Self { success: false, message: message.into() }
+    }
+}
 
 pub struct OntologicalBridge;
 
 impl OntologicalBridge {
-    pub fn execute_soul_blueprint(soul_path: &str) -> String {
+    /// Изпълнява blueprint-а само ако `<soul_path>.sig` съвпада с HMAC-а му под
+    /// ключа от AETERNA_SOUL_KEY. `allow_unsigned` пропуска проверката.
+    pub fn execute_soul_blueprint(soul_path: &str, allow_unsigned: bool) -> BridgeResult {
+        let key = std::env::var(SOUL_KEY_ENV).ok();
+        let key = key.as_deref().map(str::as_bytes);
+        Self::execute_soul_blueprint_with(soul_path, key, allow_unsigned)
+    }
+
+    pub fn execute_soul_blueprint_with(
+        soul_path: &str,
+        key: Option<&[u8]>,
+        allow_unsigned: bool,
+    ) -> BridgeResult {
         println!("[ONTOLOGICAL_BRIDGE] Reading Soul Blueprint: {}", soul_path);
 
         let content = match std::fs::read_to_string(soul_path) {
             Ok(c) => c,
-            Err(e) => return format!("FILE_ACCESS_ERROR: {}", e),
+            Err(e) => return BridgeResult::fail(format!("FILE_ACCESS_ERROR: {}", e)),
         };
 
+        if allow_unsigned {
+            println!("⚠️ [ONTOLOGICAL_BRIDGE] UNSAFE: Signature check skipped for {}", soul_path);
+        } else if let Err(reason) = Self::verify_signature(soul_path, content.as_bytes(), key) {
+            return BridgeResult::fail(format!("SOUL_SIGNATURE_REJECTED: {}", reason));
+        }
+
         // 1. Parse .soul to AST
-        let ast = parse_soul(&content);
+        let ast = match parse_soul(&content) {
+            Ok(ast) => ast,
+            Err(e) => return BridgeResult::fail(format!("SOUL_PARSE_ERROR: {}", e)),
+        };
 
         // 2. Compile AST to Bytecode
-        let bytecode = SoulCompiler::compile(ast);
+        let bytecode = match SoulCompiler::compile(ast) {
+            Ok(bytecode) => bytecode,
+            Err(e) => return BridgeResult::fail(format!("SOUL_COMPILE_ERROR: {}", e)),
+        };
 
         // 3. Execute Bytecode in the Aeterna VM
-        let mut vm = VirtualMachine::new(bytecode);
-        vm.run();
+        Self::run_bounded(bytecode, |vm| {
+            format!(
+                "RENOVATION_SUCCESS: Reality patched via Soul Blueprint. Patches: [{}]",
+                Self::describe_patches(vm)
+            )
+        })
+    }
+
+    /// Пуска програмата с BRIDGE_STEP_BUDGET и BRIDGE_OUTPUT_CAP, така че
+    /// безкраен цикъл връща VM_TIMEOUT вместо да блокира командата.
+    fn run_bounded(
+        program: Vec<AeternaOpcode>,
+        on_success: impl FnOnce(&VirtualMachine) -> String,
+    ) -> BridgeResult {
+        let mut vm = VirtualMachine::new(program)
+            .with_step_budget(BRIDGE_STEP_BUDGET)
+            .with_output_cap(BRIDGE_OUTPUT_CAP);
+        match vm.run() {
+            Ok(()) => BridgeResult::ok(on_success(&vm)),
+            Err(e @ VmError::StepBudgetExhausted { .. }) => {
+                BridgeResult::fail(format!("VM_TIMEOUT: {}", e))
+            }
+            Err(e) => BridgeResult::fail(format!("VM_FAULT: {}", e)),
+        }
+    }
 
-        "RENOVATION_SUCCESS: Reality patched via Soul Blueprint.".to_string()
+    fn verify_signature(soul_path: &str, content: &[u8], key: Option<&[u8]>) -> Result<(), String> {
+        let key = key.ok_or_else(|| format!("{} is not set", SOUL_KEY_ENV))?;
+        let sig_path = format!("{}.sig", soul_path);
+        let sig_hex = std::fs::read_to_string(&sig_path)
+            .map_err(|e| format!("{}: {}", sig_path, e))?;
+        let signature = hex::decode(sig_hex.trim())
+            .map_err(|_| "malformed signature".to_string())?;
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC can take key of any size");
+        mac.update(content);
+        mac.verify_slice(&signature).map_err(|_| "signature does not match".to_string())
+    }
+
+    /// Приложените от VM-а hotfix-и като "#bug fix, ...".
+    fn describe_patches(vm: &VirtualMachine) -> String {
+        vm.patches()
+            .iter()
+            .map(|(bug_id, fix)| format!("#{} {}", bug_id, fix))
+            .collect::<Vec<_>>()
+            .join(", ")
     }
 
-    pub fn execute_direct_command(cmd: &str) -> String {
+    pub fn execute_direct_command(cmd: &str) -> BridgeResult {
         println!("[ONTOLOGICAL_BRIDGE] Executing Direct Shift: {}", cmd);
         // Translation from high-level architect command to VM execution
         match cmd.to_uppercase().as_str() {
-            "SHIFT" | "ONTOLOGICAL_SHIFT" => {
-                let mut vm = VirtualMachine::new(vec![
-                    aeterna_node::vm::bytecode::AeternaOpcode::ONTOLOGICAL_SHIFT(0x4121),
-                    aeterna_node::vm::bytecode::AeternaOpcode::HALT,
-                ]);
-                vm.run();
-                "✨ Reality shifted successfully.".to_string()
-            }
-            "HEAL" | "PATCH_REALITY" => {
-                let mut vm = VirtualMachine::new(vec![
-                    aeterna_node::vm::bytecode::AeternaOpcode::PATCH_REALITY(
-                        404,
-                        "AETERNA_CORE_STABILITY".to_string(),
-                    ),
-                    aeterna_node::vm::bytecode::AeternaOpcode::HALT,
-                ]);
-                vm.run();
-                "🩺 Reality patched.".to_string()
-            }
-            _ => "UNKNOWN_ONTOLOGICAL_COMMAND".to_string(),
+            "SHIFT" | "ONTOLOGICAL_SHIFT" => Self::run_bounded(
+                vec![AeternaOpcode::ONTOLOGICAL_SHIFT(0x4121), AeternaOpcode::HALT],
+                |_| "✨ Reality shifted successfully.".to_string(),
+            ),
+            "HEAL" | "PATCH_REALITY" => Self::run_bounded(
+                vec![
+                    AeternaOpcode::PATCH_REALITY(404, "AETERNA_CORE_STABILITY".to_string()),
+                    AeternaOpcode::HALT,
+                ],
+                |vm| format!("🩺 Reality patched: {}.", Self::describe_patches(vm)),
+            ),
+            _ => BridgeResult::fail("UNKNOWN_ONTOLOGICAL_COMMAND"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GENESIS: &str = include_str!("../../tests/fixtures/genesis.soul");
+    const KEY: &[u8] = b"architect-key";
+
+    fn write_blueprint(signature: Option<String>) -> (std::path::PathBuf, String) {
+        let dir = std::env::temp_dir().join(format!("lwas_soul_sig_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("genesis.soul");
+        std::fs::write(&path, GENESIS).unwrap();
+        if let Some(sig) = signature {
+            std::fs::write(dir.join("genesis.soul.sig"), sig).unwrap();
         }
+        (dir, path.to_string_lossy().into_owned())
+    }
+
+    #[test]
+    fn test_signed_blueprint_executes() {
+        let (dir, path) = write_blueprint(Some(sign_soul(GENESIS.as_bytes(), KEY)));
+
+        let result = OntologicalBridge::execute_soul_blueprint_with(&path, Some(KEY), false);
+
+        assert!(result.success, "{}", result.message);
+        assert!(result.message.starts_with("RENOVATION_SUCCESS"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_tampered_or_unsigned_blueprint_is_rejected() {
+        let (dir, path) = write_blueprint(Some(sign_soul(b"manifold other {}", KEY)));
+        let result = OntologicalBridge::execute_soul_blueprint_with(&path, Some(KEY), false);
+        assert_eq!(result, BridgeResult::fail("SOUL_SIGNATURE_REJECTED: signature does not match"));
+        let _ = std::fs::remove_dir_all(dir);
+
+        let (dir, path) = write_blueprint(None);
+        let result = OntologicalBridge::execute_soul_blueprint_with(&path, Some(KEY), false);
+        assert!(!result.success);
+        assert!(result.message.starts_with("SOUL_SIGNATURE_REJECTED"));
+
+        let result = OntologicalBridge::execute_soul_blueprint_with(&path, Some(KEY), true);
+        assert!(result.success, "{}", result.message);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_looping_blueprint_fails_within_budget() {
+        let result = OntologicalBridge::run_bounded(vec![AeternaOpcode::JUMP(0)], |_| unreachable!());
+
+        assert!(!result.success);
+        assert_eq!(
+            result.message,
+            format!("VM_TIMEOUT: Step budget of {} instructions exhausted", BRIDGE_STEP_BUDGET)
+        );
     }
 }
@@ -2,12 +2,65 @@ use crate::prelude::*;
 use crate::omega::veritas::{VeritasLayer, LogicProof};
 use crate::omega::rl::SovereignRL;
 
+/// Реакция на висока ентропия: изпълнява се, когато ентропията достигне `threshold`.
+pub struct EntropyAction {
+    pub name: String,
+    pub threshold: f64,
+    action: Box<dyn Fn(&VectorSpaceHeap) + Send + Sync>,
+}
+
+/// Регистър с поведения за автономния цикъл (колапс, компактиране, аларми...).
+#[derive(Default)]
+pub struct EntropyActionRegistry {
+    actions: Vec<EntropyAction>,
+}
+
+impl EntropyActionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Регистър с колапс по подразбиране при ентропия 0.7: премахва
+    /// наказаните точки (q < 0), чийто резонанс е угаснал под 0.5.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register("collapse", 0.7, |vsh| {
+            let removed = vsh.compact(0.0, 0.5);
+            crate::sovereign_log!(warn, "⚠️  COLLAPSE: {} DECOHERED POINTS REMOVED.", removed);
+        });
+        registry
+    }
+
+    pub fn register<F>(&mut self, name: &str, threshold: f64, action: F)
+    where
+        F: Fn(&VectorSpaceHeap) + Send + Sync + 'static,
+    {
+        self.actions.push(EntropyAction {
+            name: name.to_string(),
+            threshold,
+            action: Box::new(action),
+        });
+    }
+
+    /// Изпълнява всички действия с праг <= `entropy`; връща имената им.
+    pub fn fire(&self, vsh: &VectorSpaceHeap, entropy: f64) -> Vec<&str> {
+        self.actions
+            .iter()
+            .filter(|a| entropy >= a.threshold)
+            .map(|a| {
+                (a.action)(vsh);
+                a.name.as_str()
+            })
+            .collect()
+    }
+}
+
 pub struct AeternaOracle;
 
 impl AeternaOracle {
     /// ЕКЗЕКУЦИЯ: Изпълнява суверенна команда след валидация през Veritas.
     pub async fn execute_sovereign_command(vsh: &Arc<VectorSpaceHeap>, input: &str) -> String {
-        println!("🧠 ORACLE: PROCESSING INTENT '{}'...", input);
+        crate::sovereign_log!(info, "🧠 ORACLE: PROCESSING INTENT '{}'...", input);
         
         let proof = LogicProof {
             intent: input.to_string(),
@@ -24,35 +77,103 @@ impl AeternaOracle {
     }
 
     /// АВТОНОМЕН ЦИКЪЛ: Агентът сканира VSH и взема решения.
-    pub async fn run_autonomous_loop(vsh: Arc<VectorSpaceHeap>) {
-        println!("🤖 AUTONOMOUS AGENT ACTIVE. WATCHING THE 2B NODES...");
+    pub async fn run_autonomous_loop(
+        vsh: Arc<VectorSpaceHeap>,
+        actions: EntropyActionRegistry,
+        mut shutdown: tokio::sync::watch::Receiver<bool>,
+    ) {
+        crate::sovereign_log!(info, "🤖 AUTONOMOUS AGENT ACTIVE. WATCHING THE 2B NODES...");
         loop {
             let state = vsh.get_state();
-            if state.entropy > 0.7 {
-                println!("⚠️  HIGH ENTROPY DETECTED ({:.4}). INITIATING COLLAPSE...", state.entropy);
+            let fired = actions.fire(&vsh, state.entropy);
+            if !fired.is_empty() {
+                crate::sovereign_log!(
+                    warn,
+                    "⚠️  HIGH ENTROPY DETECTED ({:.4}). ACTIONS: {:?}",
+                    state.entropy,
+                    fired
+                );
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(std::time::Duration::from_secs(10)) => {}
+                _ = shutdown.wait_for(|stop| *stop) => break,
             }
-            tokio::time::sleep(std::time::Duration::from_secs(10)).await;
         }
+        crate::sovereign_log!(info, "🤖 AUTONOMOUS AGENT STOOD DOWN.");
     }
 
     /// ИНЖЕКТИРАНЕ НА АКСИОМА: Добавяне на нови знания в VSH.
-    pub fn inject_axiom(vsh: &VectorSpaceHeap, category: &str, weight: f32) {
+    pub fn inject_axiom(vsh: &VectorSpaceHeap, category: &str, weight: f32) -> SovereignResult<Uuid> {
         let metadata = format!("AXIOM_{}_{}", category, Uuid::new_v4());
         let coordinates = vec![weight; 128]; 
-        vsh.allocate(metadata, coordinates);
+        vsh.allocate(metadata, coordinates)
     }
 
     /// WEALTH BRIDGE: Свързва успеха на AI-то с твоя капитал.
     pub fn process_rl_reward(vsh: &VectorSpaceHeap, node_id: Uuid, success: bool) {
         let reward = if success { 25.0 } else { -15.0 };
         
-        if let Some(mut point) = vsh.points.get_mut:: <Uuid> (&node_id) {
+        // RefMut държи шарда заключен, така че update_node пише директно в записа на VSH.
+        if let Some(mut point) = vsh.points.get_mut(&node_id) {
             let rl = SovereignRL::new();
-            rl.update_node(point.value_mut(), reward, 1.618); 
-            
+            rl.update_node(point.value_mut(), reward, 1.618);
+
             if success {
-                println!("💎 RL_SUCCESS: NODE {:?} ENTRENCHED. EQUITY GAINED.", node_id);
+                crate::sovereign_log!(info, "💎 RL_SUCCESS: NODE {:?} ENTRENCHED. EQUITY GAINED.", node_id);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_rl_reward_is_written_back_to_the_point() {
+        let vsh = VectorSpaceHeap::new().unwrap();
+        let id = vsh.allocate("TRADE".into(), vec![1.0; 4]).unwrap();
+        let before = vsh.points.get(&id).unwrap().q_value;
+
+        AeternaOracle::process_rl_reward(&vsh, id, true);
+
+        let point = vsh.points.get(&id).unwrap();
+        assert_eq!(point.visits, 1);
+        assert!(point.q_value > before);
+        assert_eq!(point.success_rate, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_autonomous_loop_fires_registered_action() {
+        let vsh = Arc::new(VectorSpaceHeap::new().unwrap());
+        let id = vsh.allocate("CHAOS".into(), vec![1.0; 4]).unwrap();
+        vsh.points.get_mut(&id).unwrap().entropy = 0.95;
+
+        let fired = Arc::new(AtomicUsize::new(0));
+        let spy = Arc::clone(&fired);
+        let mut actions = EntropyActionRegistry::new();
+        actions.register("spy", 0.9, move |_| {
+            spy.fetch_add(1, Ordering::SeqCst);
+        });
+        actions.register("dormant", 0.99, |_| panic!("threshold not reached"));
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let agent = tokio::spawn(AeternaOracle::run_autonomous_loop(
+            Arc::clone(&vsh),
+            actions,
+            shutdown_rx,
+        ));
+
+        for _ in 0..50 {
+            if fired.load(Ordering::SeqCst) > 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        shutdown_tx.send(true).unwrap();
+        agent.await.unwrap();
+
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,157 @@
+// lwas_core/src/omega/response_cache.rs
+// ARCHITECT: Dimitar Prodromov | AUTHORITY: AETERNA
+// STATUS: RESPONSE_CACHE_ACTIVE // MODE: EXTERNAL_CALL_DAMPENING
+
+use crate::prelude::*;
+use serde_json::Value;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_TTL: Duration = Duration::from_secs(30);
+pub const DEFAULT_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+/// Кеш за отговори от външни API-та (Binance, WealthBridge), ключиран по endpoint + параметри.
+pub struct ResponseCache {
+    entries: DashMap<String, (Instant, Value)>,
+    ttl: Duration,
+    capacity: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResponseCache {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            entries: DashMap::new(),
+            ttl,
+            capacity,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Общият кеш на процеса, споделен от мостовете.
+    pub fn shared() -> &'static ResponseCache {
+        static SHARED: OnceLock<ResponseCache> = OnceLock::new();
+        SHARED.get_or_init(|| ResponseCache::new(DEFAULT_TTL, DEFAULT_CAPACITY))
+    }
+
+    fn key(endpoint: &str, params: &str) -> String {
+        format!("{}?{}", endpoint, params)
+    }
+
+    pub fn get(&self, endpoint: &str, params: &str) -> Option<Value> {
+        let key = Self::key(endpoint, params);
+        let fresh = self
+            .entries
+            .get(&key)
+            .filter(|e| e.0.elapsed() < self.ttl)
+            .map(|e| e.1.clone());
+
+        match fresh {
+            Some(v) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(v)
+            }
+            None => {
+                self.entries.remove(&key);
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    pub fn insert(&self, endpoint: &str, params: &str, value: Value) {
+        if self.entries.len() >= self.capacity {
+            self.evict();
+        }
+        self.entries
+            .insert(Self::key(endpoint, params), (Instant::now(), value));
+    }
+
+    /// Връща кеширания отговор или вика `fetch` и запомня резултата.
+    pub async fn get_or_fetch<F, Fut>(
+        &self,
+        endpoint: &str,
+        params: &str,
+        fetch: F,
+    ) -> SovereignResult<Value>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = SovereignResult<Value>>,
+    {
+        if let Some(v) = self.get(endpoint, params) {
+            return Ok(v);
+        }
+        let value = fetch().await?;
+        self.insert(endpoint, params, value.clone());
+        Ok(value)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.len(),
+        }
+    }
+
+    /// Първо изхвърля изтеклите записи; ако няма такива - най-стария.
+    fn evict(&self) {
+        self.entries.retain(|_, e| e.0.elapsed() < self.ttl);
+        if self.entries.len() < self.capacity {
+            return;
+        }
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|e| e.value().0)
+            .map(|e| e.key().clone());
+        if let Some(key) = oldest {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[tokio::test]
+    async fn test_second_request_within_ttl_is_a_hit() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 8);
+        let calls = AtomicUsize::new(0);
+        let fetch = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(serde_json::json!({ "price": "142.00" }))
+        };
+
+        let first = cache.get_or_fetch("ticker/price", "symbol=SOLUSDC", fetch).await.unwrap();
+        let second = cache.get_or_fetch("ticker/price", "symbol=SOLUSDC", fetch).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1, entries: 1 });
+    }
+
+    #[test]
+    fn test_capacity_evicts_oldest() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 2);
+        cache.insert("a", "", Value::Null);
+        cache.insert("b", "", Value::Null);
+        cache.insert("c", "", Value::Null);
+
+        assert_eq!(cache.stats().entries, 2);
+        assert!(cache.get("a", "").is_none());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::prelude::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::RwLock;
 use std::fs;
 use crate::omega::generator::{SovereignGenerator, GeneratedAsset};
@@ -9,77 +10,262 @@ pub struct ScribeReport {
     pub actions_performed: usize,
     pub files_modified: usize,
     pub equity_yield: f64,
+    /// Какво се е случило с всеки файл от находките.
+    pub file_outcomes: Vec<FileOutcome>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FileAction {
+    Harmonized,
+    /// Dry run: предложението е в `*.shadow.rs`, оригиналът е непокътнат.
+    Proposed,
+    Skipped,
+    Failed,
+}
+
+/// Резултатът от един пург: изходът за всеки файл и журналът за rollback.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PurgeRun {
+    /// Id на журнала под `.scribe_journal/`; `None`, ако нищо не е презаписано.
+    pub run_id: Option<String>,
+    pub outcomes: Vec<FileOutcome>,
+}
+
+/// Един запис в `manifest.json` на журнала.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct JournalEntry {
+    pub original: PathBuf,
+    pub backup: PathBuf,
+}
+
+pub const JOURNAL_DIR: &str = ".scribe_journal";
+const JOURNAL_MANIFEST: &str = "manifest.json";
+
+fn io_err(e: impl std::fmt::Display) -> SovereignError {
+    SovereignError::IoError(e.to_string())
+}
+
+/// Дали пургът пише върху файловете или само предлага промените.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScribeMode {
+    #[default]
+    Apply,
+    DryRun,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FileOutcome {
+    pub path: PathBuf,
+    pub action: FileAction,
+    pub reason: String,
 }
 
 pub struct SovereignScribe {
     pub audit: Arc<RwLock<SovereignAudit>>,
     pub vsh: Arc<VectorSpaceHeap>,
     pub generator: SovereignGenerator,
+    /// Натрупан equity в центове; споделя се между сървъра и Tauri без заключване.
+    equity_cents: Arc<AtomicU64>,
+    /// Коренът, под който хирургията чете и пише (src/, assets/).
+    workspace_root: PathBuf,
 }
 
 impl SovereignScribe {
     pub fn new(audit: Arc<RwLock<SovereignAudit>>, vsh: Arc<VectorSpaceHeap>) -> Self {
+        Self::with_workspace(audit, vsh, Path::new("."))
+    }
+
+    pub fn with_workspace(
+        audit: Arc<RwLock<SovereignAudit>>,
+        vsh: Arc<VectorSpaceHeap>,
+        workspace_root: &Path,
+    ) -> Self {
         Self { 
             audit, 
             vsh,
-            generator: SovereignGenerator::new(),
+            generator: SovereignGenerator::in_workspace(workspace_root),
+            equity_cents: Arc::new(AtomicU64::new(0)),
+            workspace_root: workspace_root.to_path_buf(),
         }
     }
 
+    pub fn workspace_root(&self) -> &Path {
+        &self.workspace_root
+    }
+
     /// АКТИВНА ХИРУРГИЯ: Изпълнява автономен рефакторинг въз основа на одит.
     pub async fn perform_surgery(&self) -> Result<ScribeReport, String> {
-        println!("✍️  THE SCRIBE: INITIATING ACTIVE SURGERY CYCLE...");
+        crate::sovereign_log!(info, "✍️  THE SCRIBE: INITIATING ACTIVE SURGERY CYCLE...");
         
-        let files_purged = self.execute_first_purge().await.map_err(|e| e.to_string())?;
+        let file_outcomes = self
+            .execute_first_purge(ScribeMode::Apply)
+            .await
+            .map_err(|e| e.to_string())?
+            .outcomes;
+        let files_purged = file_outcomes
+            .iter()
+            .filter(|o| o.action == FileAction::Harmonized)
+            .count();
         
         // ДЕМО КЛЪСТЕР ЗА ГЕНЕРИРАНЕ
         let cluster_name = "Optimization_Gem";
-        let mock_files = vec![std::path::PathBuf::from("./src/lib.rs")];
+        let mock_files = vec![self.workspace_root.join("src/lib.rs")];
         let _ = self.generator.package_cluster(cluster_name, mock_files, &self.vsh).await.map_err(|e| e.to_string())?;
 
         let report = ScribeReport {
             actions_performed: files_purged,
             files_modified: files_purged,
             equity_yield: self.calculate_equity_yield(files_purged),
+            file_outcomes,
         };
+        let cents = (report.equity_yield * 100.0).round().max(0.0) as u64;
+        self.equity_cents.fetch_add(cents, Ordering::Relaxed);
 
         Ok(report)
     }
 
     /// ПЪРВИЯТ ПУРГ: Генериране на рефакториран код и атомно записване.
-    pub async fn execute_first_purge(&self) -> SovereignResult<usize> {
-        println!("✍️  THE SCRIBE: INITIATING EMPIRE-WIDE HARMONIZATION...");
-        let mut fixed_count = 0;
+    /// Неуспешен файл не спира цикъла - записва се като `Failed`.
+    /// В `ScribeMode::DryRun` предложението остава в `*.shadow.rs` до оригинала.
+    /// Преди всяко презаписване оригиналът се копира в `.scribe_journal/<run_id>/`.
+    pub async fn execute_first_purge(&self, mode: ScribeMode) -> SovereignResult<PurgeRun> {
+        crate::sovereign_log!(info, "✍️  THE SCRIBE: INITIATING EMPIRE-WIDE HARMONIZATION...");
+        let mut outcomes = Vec::new();
         let audit = self.audit.read().await;
+        let run_id = format!(
+            "{}-{}",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S"),
+            &Uuid::new_v4().simple().to_string()[..8]
+        );
+        let mut journal = Vec::new();
 
         for finding in &audit.findings {
-            if finding.f_type == FindingType::Redundancy {
-                let suggestion = &finding.suggestion;
-                let optimized_code = format!("// HARMONIZED BY THE SCRIBE\n// Original Intent: {}\n{}", suggestion, "pub fn stabilized_logic() { println!(\"Resonance achieved.\"); }");
-                
-                if let Some(target_file) = finding.files.first() {
-                    let shadow_path = target_file.with_extension("shadow.rs");
-                    fs::write(&shadow_path, optimized_code).map_err(|e| SovereignError::IoError(e.to_string()))?;
-
-                    if true { 
-                        fs::rename(&shadow_path, target_file).map_err(|e| SovereignError::IoError(e.to_string()))?; 
-                        fixed_count += 1;
-                        println!("✅ HARMONIZED: {:?}", target_file);
-                    } else {
-                        fs::remove_file(&shadow_path).map_err(|e| SovereignError::IoError(e.to_string()))?; 
+            let Some(target_file) = finding.files.first() else {
+                continue;
+            };
+            if finding.f_type != FindingType::Redundancy {
+                outcomes.push(FileOutcome {
+                    path: target_file.clone(),
+                    action: FileAction::Skipped,
+                    reason: format!("{:?} findings are not harmonized", finding.f_type),
+                });
+                continue;
+            }
+
+            let optimized_code = format!("// HARMONIZED BY THE SCRIBE\n// Original Intent: {}\n{}", finding.suggestion, "pub fn stabilized_logic() { println!(\"Resonance achieved.\"); }");
+            let shadow_path = target_file.with_extension("shadow.rs");
+
+            if mode == ScribeMode::DryRun {
+                let outcome = match fs::write(&shadow_path, optimized_code) {
+                    Ok(()) => {
+                        crate::sovereign_log!(info, "🔎 PROPOSED: {:?} -> {:?}", target_file, shadow_path);
+                        FileOutcome {
+                            path: target_file.clone(),
+                            action: FileAction::Proposed,
+                            reason: format!("proposal written to {}", shadow_path.display()),
+                        }
                     }
+                    Err(e) => FileOutcome {
+                        path: target_file.clone(),
+                        action: FileAction::Failed,
+                        reason: e.to_string(),
+                    },
+                };
+                outcomes.push(outcome);
+                continue;
+            }
+
+            let written = self
+                .journal_original(&run_id, &mut journal, target_file)
+                .and_then(|_| fs::write(&shadow_path, optimized_code))
+                .and_then(|_| fs::rename(&shadow_path, target_file));
+
+            match written {
+                Ok(()) => {
+                    crate::sovereign_log!(info, "✅ HARMONIZED: {:?}", target_file);
+                    outcomes.push(FileOutcome {
+                        path: target_file.clone(),
+                        action: FileAction::Harmonized,
+                        reason: finding.suggestion.clone(),
+                    });
+                }
+                Err(e) => {
+                    let _ = fs::remove_file(&shadow_path);
+                    outcomes.push(FileOutcome {
+                        path: target_file.clone(),
+                        action: FileAction::Failed,
+                        reason: e.to_string(),
+                    });
                 }
             }
         }
-        Ok(fixed_count)
+        Ok(PurgeRun {
+            run_id: (!journal.is_empty()).then_some(run_id),
+            outcomes,
+        })
+    }
+
+    fn journal_path(&self, run_id: &str) -> PathBuf {
+        self.workspace_root.join(JOURNAL_DIR).join(run_id)
+    }
+
+    /// Копира оригинала в журнала и пренаписва manifest-а, преди файлът да бъде пипнат.
+    fn journal_original(
+        &self,
+        run_id: &str,
+        journal: &mut Vec<JournalEntry>,
+        original: &Path,
+    ) -> std::io::Result<()> {
+        let dir = self.journal_path(run_id);
+        fs::create_dir_all(&dir)?;
+        let backup = dir.join(format!("{}.bak", journal.len()));
+        fs::copy(original, &backup)?;
+        journal.push(JournalEntry {
+            original: original.to_path_buf(),
+            backup,
+        });
+        let manifest = serde_json::to_vec_pretty(journal).map_err(std::io::Error::other)?;
+        fs::write(dir.join(JOURNAL_MANIFEST), manifest)
+    }
+
+    /// Връща всички файлове от журнала `run_id` в състоянието им преди пурга.
+    /// Обхожда журнала отзад напред: ако файл е презаписан два пъти, последен
+    /// се възстановява най-ранният backup, т.е. оригиналът.
+    pub fn rollback(&self, run_id: &str) -> SovereignResult<usize> {
+        if run_id.is_empty() || run_id.contains(['/', '\\']) || run_id.contains("..") {
+            return Err(SovereignError::SecurityViolation);
+        }
+        let manifest = fs::read(self.journal_path(run_id).join(JOURNAL_MANIFEST)).map_err(io_err)?;
+        let entries: Vec<JournalEntry> = serde_json::from_slice(&manifest).map_err(io_err)?;
+
+        for entry in entries.iter().rev() {
+            fs::copy(&entry.backup, &entry.original).map_err(io_err)?;
+            crate::sovereign_log!(info, "⏪ RESTORED: {:?}", entry.original);
+        }
+        Ok(entries.len())
     }
 
     pub fn calculate_equity_yield(&self, actions: usize) -> f64 {
         actions as f64 * 420.69 
     }
 
+    /// Натрупаният equity yield от всички хирургични цикли досега, в центове.
+    pub fn accumulated_equity_cents(&self) -> u64 {
+        self.equity_cents.load(Ordering::Relaxed)
+    }
+
+    /// Същото, в долари.
+    pub fn accumulated_equity(&self) -> f64 {
+        self.accumulated_equity_cents() as f64 / 100.0
+    }
+
+    /// Споделен брояч, за слушатели извън Scribe.
+    pub fn equity_counter(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.equity_cents)
+    }
+
     pub async fn enforce_harmony(&self, paths: Vec<PathBuf>) -> Result<(), String> {
-        println!("🔱 THE SCRIBE: ENFORCING ECOSYSTEM HARMONY...");
+        crate::sovereign_log!(info, "🔱 THE SCRIBE: ENFORCING ECOSYSTEM HARMONY...");
         for path in paths {
             if path.join("package.json").exists() {
                 self.harmonize_package_json(path.join("package.json")).await?;
@@ -105,7 +291,7 @@ impl SovereignScribe {
         // Проверка за валидност на входящия поток
         let _pkg: serde_json::Value = serde_json::from_str(data).unwrap_or(serde_json::Value::Null);
         // Продължи с имутабилното записване в .soul файла...
-        println!("🏛️ [SCRIBE]: Context entrenched.");
+        crate::sovereign_log!(info, "🏛️ [SCRIBE]: Context entrenched.");
     }
 
     pub async fn package_saas(&self, cluster_name: &str) -> SovereignResult<GeneratedAsset> {
@@ -113,3 +299,162 @@ impl SovereignScribe {
         self.generator.package_cluster(cluster_name, mock_files, &self.vsh).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(f_type: FindingType, file: PathBuf) -> AuditFinding {
+        AuditFinding {
+            id: Uuid::new_v4().to_string(),
+            f_type,
+            title: "fixture".into(),
+            files: vec![file],
+            impact_lines: 1,
+            line: None,
+            suggestion: "merge duplicates".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_purge_records_per_file_outcomes() {
+        let dir = std::env::temp_dir().join(format!("lwas_scribe_outcomes_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let twin = dir.join("twin.rs");
+        let gap = dir.join("gap.rs");
+        fs::write(&twin, "fn twin() {}\n").unwrap();
+        fs::write(&gap, "// TODO\n").unwrap();
+        let missing = dir.join("no_such_dir").join("ghost.rs");
+
+        let mut audit = SovereignAudit::new();
+        audit.findings = vec![
+            finding(FindingType::Redundancy, twin.clone()),
+            finding(FindingType::LogicGap, gap.clone()),
+            finding(FindingType::Redundancy, missing.clone()),
+        ];
+        let scribe = SovereignScribe::with_workspace(
+            Arc::new(RwLock::new(audit)),
+            Arc::new(VectorSpaceHeap::new().unwrap()),
+            &dir,
+        );
+
+        let outcomes = scribe.execute_first_purge(ScribeMode::Apply).await.unwrap().outcomes;
+        let actions: Vec<(PathBuf, FileAction)> =
+            outcomes.iter().map(|o| (o.path.clone(), o.action)).collect();
+
+        assert_eq!(
+            actions,
+            vec![
+                (twin.clone(), FileAction::Harmonized),
+                (gap.clone(), FileAction::Skipped),
+                (missing, FileAction::Failed),
+            ]
+        );
+        assert!(fs::read_to_string(&twin).unwrap().starts_with("// HARMONIZED"));
+        assert_eq!(fs::read_to_string(&gap).unwrap(), "// TODO\n");
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_leaves_original_and_writes_shadow() {
+        let dir = std::env::temp_dir().join(format!("lwas_scribe_dry_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let twin = dir.join("twin.rs");
+        fs::write(&twin, "fn twin() {}\n").unwrap();
+
+        let mut audit = SovereignAudit::new();
+        audit.findings = vec![finding(FindingType::Redundancy, twin.clone())];
+        let scribe = SovereignScribe::with_workspace(
+            Arc::new(RwLock::new(audit)),
+            Arc::new(VectorSpaceHeap::new().unwrap()),
+            &dir,
+        );
+
+        let run = scribe.execute_first_purge(ScribeMode::DryRun).await.unwrap();
+
+        assert_eq!(run.outcomes[0].action, FileAction::Proposed);
+        assert_eq!(run.run_id, None);
+        assert_eq!(fs::read(&twin).unwrap(), b"fn twin() {}\n");
+        let shadow = dir.join("twin.shadow.rs");
+        assert!(fs::read_to_string(shadow).unwrap().starts_with("// HARMONIZED"));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_restores_purged_file_bytes() {
+        let dir = std::env::temp_dir().join(format!("lwas_scribe_rollback_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let twin = dir.join("twin.rs");
+        let original = b"fn twin() {\r\n    // keep me\r\n}\n".to_vec();
+        fs::write(&twin, &original).unwrap();
+
+        let mut audit = SovereignAudit::new();
+        audit.findings = vec![finding(FindingType::Redundancy, twin.clone())];
+        let scribe = SovereignScribe::with_workspace(
+            Arc::new(RwLock::new(audit)),
+            Arc::new(VectorSpaceHeap::new().unwrap()),
+            &dir,
+        );
+
+        let run = scribe.execute_first_purge(ScribeMode::Apply).await.unwrap();
+        assert_ne!(fs::read(&twin).unwrap(), original);
+
+        let run_id = run.run_id.expect("purge must journal the overwritten file");
+        assert_eq!(scribe.rollback(&run_id).unwrap(), 1);
+        assert_eq!(fs::read(&twin).unwrap(), original);
+        assert!(matches!(scribe.rollback("../escape"), Err(SovereignError::SecurityViolation)));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_restores_original_after_repeated_findings() {
+        let dir = std::env::temp_dir().join(format!("lwas_scribe_rollback_twice_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let twin = dir.join("twin.rs");
+        let original = b"fn twin() {}\n".to_vec();
+        fs::write(&twin, &original).unwrap();
+
+        let mut audit = SovereignAudit::new();
+        audit.findings = vec![
+            finding(FindingType::Redundancy, twin.clone()),
+            finding(FindingType::Redundancy, twin.clone()),
+        ];
+        let scribe = SovereignScribe::with_workspace(
+            Arc::new(RwLock::new(audit)),
+            Arc::new(VectorSpaceHeap::new().unwrap()),
+            &dir,
+        );
+
+        let run = scribe.execute_first_purge(ScribeMode::Apply).await.unwrap();
+        let run_id = run.run_id.expect("purge must journal the overwritten file");
+
+        assert_eq!(scribe.rollback(&run_id).unwrap(), 2);
+        assert_eq!(fs::read(&twin).unwrap(), original);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_surgery_cycles_accumulate_equity() {
+        let dir = std::env::temp_dir().join(format!("lwas_scribe_equity_{}", Uuid::new_v4()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "pub fn anchor() {}\n").unwrap();
+        let mut audit = SovereignAudit::new();
+        audit.findings = vec![finding(FindingType::Redundancy, dir.join("src/twin.rs"))];
+        fs::write(dir.join("src/twin.rs"), "fn twin() {}\n").unwrap();
+
+        let scribe = Arc::new(SovereignScribe::with_workspace(
+            Arc::new(RwLock::new(audit)),
+            Arc::new(VectorSpaceHeap::new().unwrap()),
+            &dir,
+        ));
+        let (a, b) = tokio::join!(
+            { let s = Arc::clone(&scribe); async move { s.perform_surgery().await } },
+            { let s = Arc::clone(&scribe); async move { s.perform_surgery().await } },
+        );
+        let (a, b) = (a.unwrap(), b.unwrap());
+
+        let expected = ((a.equity_yield * 100.0).round() + (b.equity_yield * 100.0).round()) as u64;
+        assert_eq!(scribe.accumulated_equity_cents(), expected);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
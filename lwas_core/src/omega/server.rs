@@ -1,46 +1,161 @@
 use crate::prelude::*;
 use crate::omega::scribe::SovereignScribe;
 use crate::omega::oracle::AeternaOracle;
+use crate::omega::response_cache::ResponseCache;
+use crate::security::safe_path::SafePath;
 use axum::{
     routing::{get, post},
     Router, Json, extract::State, response::IntoResponse,
 };
 use serde_json::{json, Value};
-use tokio::sync::RwLock;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use tokio::sync::{watch, RwLock};
 
 pub struct ServerState {
     pub vsh: Arc<VectorSpaceHeap>,
     pub audit: Arc<RwLock<SovereignAudit>>,
     pub enforcer: Arc<SovereignScribe>,
+    /// Всички пътища, подадени към сървъра, трябва да лежат под този корен.
+    pub workspace_root: PathBuf,
 }
 
-pub async fn start_singularity_server(state: Arc<ServerState>) {
+#[derive(Deserialize)]
+pub struct AuditRequest {
+    pub path: PathBuf,
+}
+
+pub const DEFAULT_SINGULARITY_ADDR: ([u8; 4], u16) = ([127, 0, 0, 1], 8890);
+
+pub fn default_singularity_addr() -> SocketAddr {
+    SocketAddr::from(DEFAULT_SINGULARITY_ADDR)
+}
+
+/// PEM сертификат и ключ за HTTPS. Чете се от AETERNA_TLS_CERT / AETERNA_TLS_KEY.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+impl TlsConfig {
+    /// `None`, ако някоя от двете променливи липсва - тогава сървърът остава на HTTP.
+    pub fn from_env() -> Option<Self> {
+        match (std::env::var_os("AETERNA_TLS_CERT"), std::env::var_os("AETERNA_TLS_KEY")) {
+            (Some(cert), Some(key)) => Some(Self {
+                cert_path: cert.into(),
+                key_path: key.into(),
+            }),
+            (None, None) => None,
+            (cert, _) => {
+                let missing = if cert.is_none() { "AETERNA_TLS_CERT" } else { "AETERNA_TLS_KEY" };
+                println!("⚠️ SINGULARITY SERVER: {} IS NOT SET. FALLING BACK TO HTTP.", missing);
+                None
+            }
+        }
+    }
+}
+
+/// Слуша на `addr`, докато `shutdown` не стане `true` (или подателят изчезне),
+/// след което довършва текущите заявки и се връща. С `tls` сервира HTTPS.
+/// Липсващ или невалиден сертификат, или зает адрес, се връщат като грешка.
+pub async fn start_singularity_server(
+    state: Arc<ServerState>,
+    addr: SocketAddr,
+    tls: Option<TlsConfig>,
+    mut shutdown: watch::Receiver<bool>,
+) -> SovereignResult<()> {
     use tower_http::cors::CorsLayer;
 
     let app = Router::new()
         .route("/api/status", get(get_status))
+        .route("/metrics", get(get_metrics))
+        .route("/api/equity", get(get_equity))
+        .route("/api/scribe/audit", post(run_audit_only))
         .route("/api/scribe/refactor", post(run_auto_refactor))
         .route("/api/ask", post(ask_sovereign_brain))
         .route("/api/scribe/generate", post(run_asset_generation))
         .with_state(state)
         .layer(CorsLayer::permissive());
 
-    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 8890));
+    if let Some(tls) = tls {
+        let rustls = axum_server::tls_rustls::RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path)
+            .await?;
+        let handle = axum_server::Handle::new();
+        let stopper = handle.clone();
+        tokio::spawn(async move {
+            let _ = shutdown.wait_for(|stop| *stop).await;
+            stopper.graceful_shutdown(None);
+        });
+
+        println!("🌌 SINGULARITY SERVER ONLINE AT https://{}", addr);
+        axum_server::bind_rustls(addr, rustls)
+            .handle(handle)
+            .serve(app.into_make_service())
+            .await?;
+        println!("🌌 SINGULARITY SERVER OFFLINE.");
+        return Ok(());
+    }
+
     println!("🌌 SINGULARITY SERVER ONLINE AT http://{}", addr);
 
-    let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            let _ = shutdown.wait_for(|stop| *stop).await;
+        })
+        .await?;
+    println!("🌌 SINGULARITY SERVER OFFLINE.");
+    Ok(())
 }
 
 async fn get_status(State(state): State<Arc<ServerState>>) -> impl IntoResponse {
     Json(state.vsh.get_state())
 }
 
-async fn run_auto_refactor(State(state): State<Arc<ServerState>>) -> impl IntoResponse {
+/// Натрупаният equity на Scribe от всички хирургични цикли.
+async fn get_equity(State(state): State<Arc<ServerState>>) -> Json<Value> {
+    let cents = state.enforcer.accumulated_equity_cents();
+    Json(json!({ "equity_cents": cents, "equity": cents as f64 / 100.0 }))
+}
+
+/// Броячи на кеша за външни отговори.
+async fn get_metrics() -> impl IntoResponse {
+    Json(json!({ "response_cache": ResponseCache::shared().stats() }))
+}
+
+/// Одит без хирургия: връща находките, без да пипа файловете.
+async fn run_audit_only(
+    State(state): State<Arc<ServerState>>,
+    Json(payload): Json<AuditRequest>,
+) -> Json<Value> {
+    println!("🔍 THE SCRIBE: AUDIT-ONLY SCAN OF {:?}...", payload.path);
+
+    let target = match SafePath::resolve(&state.workspace_root, &payload.path) {
+        Ok(p) => p,
+        Err(e) => return Json(json!({ "status": "ERROR", "message": e })),
+    };
+
+    let mut audit = state.audit.write().await;
+    audit.findings.clear();
+    audit.symbol_registry.clear();
+
+    match audit.run_full_audit(vec![target.into_inner()]).await {
+        Ok(stats) => Json(json!({ "status": "SUCCESS", "findings": audit.findings, "stats": stats })),
+        Err(e) => Json(json!({ "status": "ERROR", "message": e })),
+    }
+}
+
+async fn run_auto_refactor(State(state): State<Arc<ServerState>>) -> Json<Value> {
     println!("📜 THE SCRIBE: INITIATING AUTO-REFACTORING CYCLE...");
     
+    let target = match SafePath::resolve(&state.workspace_root, std::path::Path::new("src")) {
+        Ok(p) => p,
+        Err(e) => return Json(json!({ "status": "ERROR", "message": e })),
+    };
+
     let mut audit = state.audit.write().await;
-    let _ = audit.run_full_audit(vec!["./src".into()]).await;
+    let _ = audit.run_full_audit(vec![target.into_inner()]).await;
     drop(audit);
 
     match state.enforcer.perform_surgery().await {
@@ -69,3 +184,195 @@ async fn run_asset_generation(State(state): State<Arc<ServerState>>) -> impl Int
         Err(e) => Json(json!({ "status": "ERROR", "message": format!("{}", e) })),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn test_state(workspace_root: PathBuf) -> Arc<ServerState> {
+        let vsh = Arc::new(VectorSpaceHeap::new().unwrap());
+        let audit = Arc::new(RwLock::new(SovereignAudit::new()));
+        Arc::new(ServerState {
+            enforcer: Arc::new(SovereignScribe::with_workspace(
+                Arc::clone(&audit),
+                Arc::clone(&vsh),
+                &workspace_root,
+            )),
+            vsh,
+            audit,
+            workspace_root,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_audit_route_reports_without_touching_files() {
+        let dir = std::env::temp_dir().join(format!("lwas_server_audit_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = "// TODO: stabilize\nfn drift() {}\n";
+        fs::write(dir.join("drift.rs"), source).unwrap();
+
+        let Json(body) = run_audit_only(
+            State(test_state(dir.clone())),
+            Json(AuditRequest { path: dir.clone() }),
+        )
+        .await;
+
+        assert_eq!(body["status"], "SUCCESS");
+        assert!(!body["findings"].as_array().unwrap().is_empty());
+        assert_eq!(fs::read_to_string(dir.join("drift.rs")).unwrap(), source);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_audit_route_rejects_escape() {
+        let dir = std::env::temp_dir().join(format!("lwas_server_escape_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let Json(body) = run_audit_only(
+            State(test_state(dir.clone())),
+            Json(AuditRequest { path: "../".into() }),
+        )
+        .await;
+
+        assert_eq!(body["status"], "ERROR");
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_server_binds_provided_addr() {
+        let probe = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = probe.local_addr().unwrap();
+        drop(probe);
+
+        let dir = std::env::temp_dir();
+        let (_shutdown_tx, shutdown_rx) = watch::channel(false);
+        let server = tokio::spawn(start_singularity_server(test_state(dir), addr, None, shutdown_rx));
+
+        let mut connected = false;
+        for _ in 0..50 {
+            if tokio::net::TcpStream::connect(addr).await.is_ok() {
+                connected = true;
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        server.abort();
+        assert!(connected);
+    }
+
+    #[tokio::test]
+    async fn test_server_returns_after_shutdown_signal() {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let server = tokio::spawn(start_singularity_server(
+            test_state(std::env::temp_dir()),
+            SocketAddr::from(([127, 0, 0, 1], 0)),
+            None,
+            shutdown_rx,
+        ));
+
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        shutdown_tx.send(true).unwrap();
+
+        let finished = tokio::time::timeout(std::time::Duration::from_secs(5), server).await;
+        assert!(finished.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_server_serves_https_with_self_signed_cert() {
+        let dir = std::env::temp_dir().join(format!("lwas_server_tls_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let tls = TlsConfig {
+            cert_path: dir.join("cert.pem"),
+            key_path: dir.join("key.pem"),
+        };
+        fs::write(&tls.cert_path, cert.cert.pem()).unwrap();
+        fs::write(&tls.key_path, cert.key_pair.serialize_pem()).unwrap();
+
+        let probe = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = probe.local_addr().unwrap();
+        drop(probe);
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let server = tokio::spawn(start_singularity_server(
+            test_state(dir.clone()),
+            addr,
+            Some(tls),
+            shutdown_rx,
+        ));
+
+        let client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .unwrap();
+        let url = format!("https://localhost:{}/api/status", addr.port());
+        let mut status = None;
+        for _ in 0..50 {
+            if let Ok(resp) = client.get(&url).send().await {
+                status = Some(resp.status());
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        shutdown_tx.send(true).unwrap();
+        let _ = tokio::time::timeout(std::time::Duration::from_secs(5), server).await;
+        let _ = fs::remove_dir_all(dir);
+        assert_eq!(status, Some(reqwest::StatusCode::OK));
+    }
+
+    #[test]
+    fn test_io_errors_serialize_into_error_bodies() {
+        let err = SovereignError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "gone"));
+        let body = json!({ "status": "ERROR", "message": err });
+        assert_eq!(body["message"]["Io"], "gone");
+    }
+
+    #[tokio::test]
+    async fn test_missing_tls_cert_is_an_error_not_a_panic() {
+        let dir = std::env::temp_dir().join(format!("lwas_server_no_cert_{}", Uuid::new_v4()));
+        let tls = TlsConfig {
+            cert_path: dir.join("cert.pem"),
+            key_path: dir.join("key.pem"),
+        };
+        let (_shutdown_tx, shutdown_rx) = watch::channel(false);
+
+        let result = start_singularity_server(
+            test_state(std::env::temp_dir()),
+            SocketAddr::from(([127, 0, 0, 1], 0)),
+            Some(tls),
+            shutdown_rx,
+        )
+        .await;
+
+        match result {
+            Err(SovereignError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+            other => panic!("expected Io(NotFound), got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refactor_route_stays_inside_workspace() {
+        let dir = std::env::temp_dir().join(format!("lwas_server_refactor_{}", Uuid::new_v4()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        let lib = "pub fn anchor() {}\n";
+        fs::write(dir.join("src/lib.rs"), lib).unwrap();
+
+        let Json(body) = run_auto_refactor(State(test_state(dir.clone()))).await;
+
+        assert_eq!(body["status"], "SUCCESS", "{}", body);
+        let report: crate::omega::scribe::ScribeReport =
+            serde_json::from_value(body["report"].clone()).unwrap();
+        assert_eq!(report.files_modified, 0);
+        assert_eq!(fs::read_to_string(dir.join("src/lib.rs")).unwrap(), lib);
+
+        let vault: Vec<PathBuf> = fs::read_dir(dir.join("assets/sovereign_saas"))
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(vault.len(), 1);
+        assert!(vault[0].join("src/main.rs").exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
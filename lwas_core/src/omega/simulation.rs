@@ -1,20 +1,34 @@
 use crate::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 pub struct MarketSimulator {
     pub target_mrr: f64,
     pub market_volatility: f64,
+    /// Единственият източник на случайност в симулатора.
+    rng: StdRng,
 }
 
 impl MarketSimulator {
     pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// Възпроизводим симулатор: един и същ seed дава една и съща поредица от пазарни състояния.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(rng: StdRng) -> Self {
         Self {
             target_mrr: 10000.0,
             market_volatility: 0.15,
+            rng,
         }
     }
 
     /// ПРОЕКЦИЯ: Симулира пазарното представяне на генерираните активи
-    pub fn project_revenue(&self, vsh: &VectorSpaceHeap) -> f64 {
+    pub fn project_revenue(&mut self, vsh: &VectorSpaceHeap) -> f64 {
         let asset_count = vsh
             .points
             .iter()
@@ -22,7 +36,10 @@ impl MarketSimulator {
             .count();
 
         let base_revenue = asset_count as f64 * 125.50;
-        let optimized_revenue = base_revenue * 1.618;
+        let market_swing = self
+            .rng
+            .gen_range(-self.market_volatility..=self.market_volatility);
+        let optimized_revenue = base_revenue * 1.618 * (1.0 + market_swing);
 
         println!(
             "📊 SIMULATION: Projected MRR for {} assets: €{:.2}",
@@ -31,3 +48,24 @@ impl MarketSimulator {
         optimized_revenue
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_projections() {
+        let vsh = VectorSpaceHeap::new().unwrap();
+        for i in 0..4 {
+            vsh.allocate(format!("MM_SAAS:{}", i), vec![1.0]).unwrap();
+        }
+
+        let mut a = MarketSimulator::with_seed(2200);
+        let mut b = MarketSimulator::with_seed(2200);
+        let run_a: Vec<f64> = (0..5).map(|_| a.project_revenue(&vsh)).collect();
+        let run_b: Vec<f64> = (0..5).map(|_| b.project_revenue(&vsh)).collect();
+
+        assert_eq!(run_a, run_b);
+        assert!(run_a.windows(2).any(|w| w[0] != w[1]));
+    }
+}
@@ -0,0 +1,124 @@
+// lwas_core/src/omega/solana_rpc.rs
+// ARCHITECT: Dimitar Prodromov | AUTHORITY: AETERNA
+// STATUS: RPC_ABSTRACTION // MODE: LIVE_OR_MIRROR
+
+use crate::prelude::{SovereignError, SovereignResult};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature, transaction::Transaction};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// Частта от Solana RPC, която Xenon, ExecutionEngine и WealthBridge реално ползват.
+pub trait SolanaRpc {
+    fn get_balance(&self, pubkey: &Pubkey) -> SovereignResult<u64>;
+    fn get_latest_blockhash(&self) -> SovereignResult<Hash>;
+    /// Token сметките на `owner` под дадена програма.
+    fn get_token_accounts_by_owner(&self, owner: &Pubkey, program_id: Pubkey) -> SovereignResult<Vec<Pubkey>>;
+    /// Суровото (необработено) количество в token сметката, напр. "0".
+    fn get_token_account_balance(&self, account: &Pubkey) -> SovereignResult<String>;
+    fn get_account_owner(&self, account: &Pubkey) -> SovereignResult<Pubkey>;
+    fn send_and_confirm_transaction(&self, txn: &Transaction) -> SovereignResult<Signature>;
+}
+
+fn rpc_err(e: impl std::fmt::Display) -> SovereignError {
+    SovereignError::IoError(format!("SOLANA_RPC: {}", e))
+}
+
+impl SolanaRpc for RpcClient {
+    fn get_balance(&self, pubkey: &Pubkey) -> SovereignResult<u64> {
+        RpcClient::get_balance(self, pubkey).map_err(rpc_err)
+    }
+
+    fn get_latest_blockhash(&self) -> SovereignResult<Hash> {
+        RpcClient::get_latest_blockhash(self).map_err(rpc_err)
+    }
+
+    fn get_token_accounts_by_owner(&self, owner: &Pubkey, program_id: Pubkey) -> SovereignResult<Vec<Pubkey>> {
+        RpcClient::get_token_accounts_by_owner(self, owner, TokenAccountsFilter::ProgramId(program_id))
+            .map_err(rpc_err)?
+            .into_iter()
+            .map(|account| Pubkey::from_str(&account.pubkey).map_err(rpc_err))
+            .collect()
+    }
+
+    fn get_token_account_balance(&self, account: &Pubkey) -> SovereignResult<String> {
+        RpcClient::get_token_account_balance(self, account)
+            .map(|ui| ui.amount)
+            .map_err(rpc_err)
+    }
+
+    fn get_account_owner(&self, account: &Pubkey) -> SovereignResult<Pubkey> {
+        RpcClient::get_account(self, account)
+            .map(|a| a.owner)
+            .map_err(rpc_err)
+    }
+
+    fn send_and_confirm_transaction(&self, txn: &Transaction) -> SovereignResult<Signature> {
+        RpcClient::send_and_confirm_transaction(self, txn).map_err(rpc_err)
+    }
+}
+
+/// Огледален RPC с предварително зададени отговори. Не излиза в мрежата.
+#[derive(Default)]
+pub struct MockSolanaRpc {
+    pub balance: u64,
+    /// program_id -> [(token сметка, сурово количество)]
+    pub token_accounts: HashMap<Pubkey, Vec<(Pubkey, String)>>,
+    pub sent: Mutex<Vec<Transaction>>,
+}
+
+impl MockSolanaRpc {
+    pub fn with_balance(balance: u64) -> Self {
+        Self { balance, ..Default::default() }
+    }
+
+    pub fn with_token_account(mut self, program_id: Pubkey, account: Pubkey, amount: &str) -> Self {
+        self.token_accounts
+            .entry(program_id)
+            .or_default()
+            .push((account, amount.to_string()));
+        self
+    }
+}
+
+impl SolanaRpc for MockSolanaRpc {
+    fn get_balance(&self, _pubkey: &Pubkey) -> SovereignResult<u64> {
+        Ok(self.balance)
+    }
+
+    fn get_latest_blockhash(&self) -> SovereignResult<Hash> {
+        Ok(Hash::default())
+    }
+
+    fn get_token_accounts_by_owner(&self, _owner: &Pubkey, program_id: Pubkey) -> SovereignResult<Vec<Pubkey>> {
+        Ok(self
+            .token_accounts
+            .get(&program_id)
+            .map(|accounts| accounts.iter().map(|(pk, _)| *pk).collect())
+            .unwrap_or_default())
+    }
+
+    fn get_token_account_balance(&self, account: &Pubkey) -> SovereignResult<String> {
+        self.token_accounts
+            .values()
+            .flatten()
+            .find(|(pk, _)| pk == account)
+            .map(|(_, amount)| amount.clone())
+            .ok_or_else(|| rpc_err(format!("UNKNOWN_ACCOUNT {}", account)))
+    }
+
+    fn get_account_owner(&self, account: &Pubkey) -> SovereignResult<Pubkey> {
+        self.token_accounts
+            .iter()
+            .find(|(_, accounts)| accounts.iter().any(|(pk, _)| pk == account))
+            .map(|(program_id, _)| *program_id)
+            .ok_or_else(|| rpc_err(format!("UNKNOWN_ACCOUNT {}", account)))
+    }
+
+    fn send_and_confirm_transaction(&self, txn: &Transaction) -> SovereignResult<Signature> {
+        self.sent.lock().unwrap().push(txn.clone());
+        Ok(Signature::default())
+    }
+}
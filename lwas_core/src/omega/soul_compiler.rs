@@ -3,44 +3,150 @@
 // STATUS: COMPILER_ACTIVATED // MODE: SOUL_COMPILATION
 
 use aeterna_node::vm::bytecode::AeternaOpcode;
-use lwas_parser::{AstNode, EntrenchValue};
+use aeterna_node::vm::interpreter::DEFAULT_MEMBRANE_FREQUENCIES;
+use lwas_parser::AstNode;
+
+/// Максимална дълбочина на вложени manifold-и по подразбиране.
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// Глобалната ноетична честота; ползва се, когато `resonate` не назовава позната честота.
+const NOETIC_HZ: f64 = 528.0;
+
+/// Слот в таблицата на мембраната за дадена честота.
+fn membrane_slot(hz: f64) -> usize {
+    let slot_of = |target: f64| DEFAULT_MEMBRANE_FREQUENCIES.iter().position(|f| *f == target);
+    slot_of(hz).or_else(|| slot_of(NOETIC_HZ)).unwrap_or(0)
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CompileError {
+    #[error("Manifold nesting exceeds max depth {limit}")]
+    MaxDepthExceeded { limit: usize },
+}
 
 pub struct SoulCompiler;
 
 impl SoulCompiler {
-    pub fn compile(nodes: Vec<AstNode>) -> Vec<AeternaOpcode> {
+    pub fn compile(nodes: Vec<AstNode>) -> Result<Vec<AeternaOpcode>, CompileError> {
+        Self::compile_with_limit(nodes, DEFAULT_MAX_DEPTH)
+    }
+
+    /// Като `compile`, но с изрична граница за влагане на manifold-и.
+    pub fn compile_with_limit(
+        nodes: Vec<AstNode>,
+        max_depth: usize,
+    ) -> Result<Vec<AeternaOpcode>, CompileError> {
         let mut bytecode = Vec::new();
+        Self::compile_into(nodes, &mut bytecode, 0, max_depth)?;
+        bytecode.push(AeternaOpcode::HALT);
+        Ok(bytecode)
+    }
 
+    fn compile_into(
+        nodes: Vec<AstNode>,
+        bytecode: &mut Vec<AeternaOpcode>,
+        depth: usize,
+        max_depth: usize,
+    ) -> Result<(), CompileError> {
         for node in nodes {
             match node {
-                AstNode::Manifold { name, curvature } => {
-                    println!(
-                        "[SOUL_COMPILER] Defining Manifold: {} (Curvature: {})",
-                        name, curvature
-                    );
-                    // In the 2200 spec, manifolds are mapped to memory states
-                    bytecode.push(AeternaOpcode::LOAD((curvature * 1000.0) as i64));
-                    bytecode.push(AeternaOpcode::STORE(0)); // Store base curvature in slot 0
-                }
-                AstNode::Resonate { left, right } => {
-                    println!("[SOUL_COMPILER] Resonating {} with {}", left, right);
-                    bytecode.push(AeternaOpcode::RESONATE_MEMBRANE(528)); // Global Noetic frequency
-                }
-                AstNode::Collapse { name } => {
-                    println!("[SOUL_COMPILER] Collapsing Manifold: {}", name);
+                AstNode::Manifold { name, body } => {
+                    if depth >= max_depth {
+                        return Err(CompileError::MaxDepthExceeded { limit: max_depth });
+                    }
+                    println!("[SOUL_COMPILER] Defining Manifold: {}", name);
+                    Self::compile_into(body, bytecode, depth + 1, max_depth)?;
+                }
+                AstNode::Body { name, content } => {
+                    println!("[SOUL_COMPILER] Materializing Body: {}", name);
+                    bytecode.push(AeternaOpcode::DEFINE_MATTER(content));
+                }
+                AstNode::Resonate { target, frequency } => {
+                    println!("[SOUL_COMPILER] Resonating {} at {}", target, frequency);
+                    bytecode.push(AeternaOpcode::RESONATE_MEMBRANE(membrane_slot(frequency)));
+                }
+                AstNode::Collapse { target, .. } => {
+                    println!("[SOUL_COMPILER] Collapsing Manifold: {}", target);
                     bytecode.push(AeternaOpcode::INVERT_ENTROPY(100)); // Harvest energy from collapse
                 }
-                AstNode::Entrench { name, value } => {
-                    println!(
-                        "[SOUL_COMPILER] Entrenching {} with value {:?}",
-                        name, value
-                    );
+                AstNode::Entrench { key, value } => {
+                    println!("[SOUL_COMPILER] Entrenching {} with value {:?}", key, value);
                     bytecode.push(AeternaOpcode::VERIFY_TIMELINE(0x4121)); // Verify causal state
                 }
+                AstNode::Magnet { label, power } => {
+                    println!("[SOUL_COMPILER] Magnetizing '{}' (Power: {})", label, power);
+                    bytecode.push(AeternaOpcode::ONTOLOGICAL_SHIFT(power as usize));
+                }
+                AstNode::Axiom { name, expression } => {
+                    println!("[SOUL_COMPILER] Axiom {}: {}", name, expression);
+                    bytecode.push(AeternaOpcode::INVERT_LOGIC(0));
+                }
+                AstNode::Causality { cause, effect, c_type } => {
+                    println!("[SOUL_COMPILER] {} causes {} via {}", cause, effect, c_type);
+                    bytecode.push(AeternaOpcode::PATCH_REALITY(0, format!("{}->{}", cause, effect)));
+                }
+                // Декларативни възли без байткод еквивалент.
+                AstNode::Immortal { .. }
+                | AstNode::Spirit { .. }
+                | AstNode::Department { .. }
+                | AstNode::Reflect => {}
             }
         }
+        Ok(())
+    }
+}
 
-        bytecode.push(AeternaOpcode::HALT);
-        bytecode
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aeterna_node::vm::interpreter::VirtualMachine;
+    use lwas_parser::parse_soul;
+
+    #[test]
+    fn test_body_content_lands_in_matter_registry() {
+        let ast = parse_soul("manifold Forge { body Crystal { lattice = hexagonal } }").unwrap();
+        let mut vm = VirtualMachine::new(SoulCompiler::compile(ast).unwrap());
+        vm.run().unwrap();
+
+        assert_eq!(vm.defined_matter(), ["lattice = hexagonal".to_string()]);
+    }
+
+    #[test]
+    fn test_resonate_frequency_reaches_membrane() {
+        let ast = parse_soul("resonate Core 639; resonate Core 12.5;").unwrap();
+        let mut vm = VirtualMachine::new(SoulCompiler::compile(ast).unwrap());
+        vm.run().unwrap();
+
+        // Непознатите честоти падат обратно към ноетичната 528 Hz.
+        assert_eq!(vm.membrane_state(), [639.0, 528.0]);
+    }
+
+    #[test]
+    fn test_disassembly_lists_compiled_mnemonics_in_order() {
+        use aeterna_node::vm::disasm::disassemble;
+
+        let ast = parse_soul("body Core { lattice } magnet \"gravity\" 7; a causes b via FORMAL;").unwrap();
+        let listing = disassemble(&SoulCompiler::compile(ast).unwrap());
+        let mnemonics: Vec<&str> = listing
+            .lines()
+            .map(|line| line.split_whitespace().nth(1).unwrap())
+            .collect();
+
+        assert_eq!(mnemonics, ["DEFINE_MATTER", "ONTOLOGICAL_SHIFT", "PATCH_REALITY", "HALT"]);
+        assert!(listing.starts_with("0000  DEFINE_MATTER \"lattice\""));
+    }
+
+    #[test]
+    fn test_nesting_beyond_limit_is_rejected() {
+        let mut ast = vec![AstNode::Reflect];
+        for i in 0..4 {
+            ast = vec![AstNode::Manifold { name: format!("M{}", i), body: ast }];
+        }
+
+        assert_eq!(
+            SoulCompiler::compile_with_limit(ast.clone(), 3).unwrap_err(),
+            CompileError::MaxDepthExceeded { limit: 3 }
+        );
+        assert!(SoulCompiler::compile_with_limit(ast, 4).is_ok());
     }
 }
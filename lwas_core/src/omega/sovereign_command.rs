@@ -7,11 +7,11 @@ pub struct SovereignCommand;
 
 impl SovereignCommand {
     /// Изпълнява Глобално Пренаписване (Global Rewrite) чрез Легиона.
-    pub async fn command_the_infinite(directive: &str) {
+    pub async fn command_the_infinite(directive: &str, vsh: &VectorSpaceHeap) {
         println!("👑 [COMMAND]: Инициирам директива: '{}'", directive);
         
         // 1. Излъчване на честотата на суверенитета
-        AlephBroadcaster::broadcast_sovereignty();
+        AlephBroadcaster::broadcast_sovereignty(&vsh.get_state());
 
         // 2. Мобилизация на Легиона за асимилация на външни възли
         // count: 1_000_000 агенти за начална вълна
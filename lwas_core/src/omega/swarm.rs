@@ -1,6 +1,9 @@
 use crate::prelude::*;
+use async_trait::async_trait;
 use std::net::SocketAddr;
-use tokio::net::TcpStream;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
 
 pub struct SovereignNode {
     pub addr: SocketAddr,
@@ -9,31 +12,200 @@ pub struct SovereignNode {
     pub revenue_generated: f64,
 }
 
+/// Един деплой по мрежата. `key` = asset id + хеш на съдържанието, така че
+/// повторен опит със същия актив носи същия ключ.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeployRequest {
+    pub key: String,
+    pub asset_id: String,
+}
+
+impl DeployRequest {
+    pub fn new(asset_id: &str, content: &[u8]) -> Self {
+        Self {
+            key: format!("{}:{:x}", asset_id, md5::compute(content)),
+            asset_id: asset_id.to_string(),
+        }
+    }
+}
+
+/// Отговорът на възела: приложен сега или вече приложен с този ключ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeployAck {
+    Applied,
+    AlreadyApplied,
+}
+
+/// Приемащата страна: помни ключовете, които вече е приложила.
+#[derive(Default)]
+pub struct DeploymentLedger {
+    applied: DashMap<String, String>,
+}
+
+impl DeploymentLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn receive(&self, request: &DeployRequest) -> DeployAck {
+        match self.applied.entry(request.key.clone()) {
+            dashmap::mapref::entry::Entry::Occupied(_) => DeployAck::AlreadyApplied,
+            dashmap::mapref::entry::Entry::Vacant(slot) => {
+                slot.insert(request.asset_id.clone());
+                DeployAck::Applied
+            }
+        }
+    }
+
+    pub fn applied_count(&self) -> usize {
+        self.applied.len()
+    }
+
+    /// Приема `DeployRequest` редове от `listener` и отговаря с по един `DeployAck` ред.
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> SovereignResult<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let ledger = Arc::clone(&self);
+            tokio::spawn(async move {
+                if let Err(e) = ledger.handle_connection(stream).await {
+                    println!("⚠️ SWARM: DEPLOY CONNECTION DROPPED ({})", e);
+                }
+            });
+        }
+    }
+
+    async fn handle_connection(&self, stream: TcpStream) -> SovereignResult<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        while let Some(line) = lines.next_line().await? {
+            let request: DeployRequest = serde_json::from_str(&line)
+                .map_err(|e| SovereignError::LogicCollapse(e.to_string()))?;
+            let mut ack = serde_json::to_vec(&self.receive(&request))
+                .map_err(|e| SovereignError::LogicCollapse(e.to_string()))?;
+            ack.push(b'\n');
+            writer.write_all(&ack).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait DeployTransport: Send + Sync {
+    async fn deliver(&self, target: SocketAddr, request: &DeployRequest) -> SovereignResult<DeployAck>;
+}
+
+pub const DEFAULT_ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// TCP транспорт: изпраща заявката като един JSON ред и чака `DeployAck` ред обратно.
+pub struct TcpTransport {
+    pub ack_timeout: Duration,
+}
+
+impl Default for TcpTransport {
+    fn default() -> Self {
+        Self { ack_timeout: DEFAULT_ACK_TIMEOUT }
+    }
+}
+
+#[async_trait]
+impl DeployTransport for TcpTransport {
+    async fn deliver(&self, target: SocketAddr, request: &DeployRequest) -> SovereignResult<DeployAck> {
+        let mut line = serde_json::to_vec(request)
+            .map_err(|e| SovereignError::LogicCollapse(e.to_string()))?;
+        line.push(b'\n');
+
+        let exchange = async {
+            let mut stream = TcpStream::connect(target).await?;
+            stream.write_all(&line).await?;
+            let mut reply = String::new();
+            BufReader::new(stream).read_line(&mut reply).await?;
+            Ok::<_, std::io::Error>(reply)
+        };
+        let reply = tokio::time::timeout(self.ack_timeout, exchange)
+            .await
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "DEPLOY_ACK_TIMEOUT"))??;
+
+        serde_json::from_str(reply.trim())
+            .map_err(|e| SovereignError::LogicCollapse(format!("MALFORMED_DEPLOY_ACK: {}", e)))
+    }
+}
+
+/// Само мрежовите/I-O грешки си струва да се опитат отново; лоша заявка или
+/// неразбираем отговор ще се провалят по същия начин и при следващия опит.
+fn is_transient(error: &SovereignError) -> bool {
+    matches!(error, SovereignError::Io(_) | SovereignError::IoError(_))
+}
+
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+pub const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(200);
+
 pub struct SwarmCommander {
     pub nodes: Arc<DashMap<SocketAddr, SovereignNode>>,
+    /// Повторни опити след първия неуспешен деплой.
+    pub max_retries: u32,
+    /// Пауза преди първия повторен опит; удвоява се след всеки следващ.
+    pub retry_backoff: Duration,
 }
 
 impl SwarmCommander {
     pub fn new() -> Self {
-        Self { nodes: Arc::new(DashMap::new()) }
+        Self {
+            nodes: Arc::new(DashMap::new()),
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+        }
+    }
+
+    pub fn with_retry(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_backoff = backoff;
+        self
     }
 
     /// DEPLOY: Изпраща пречистен актив към суверенен възел
-    pub async fn deploy_asset(&self, asset_id: &str, target_addr: SocketAddr) -> SovereignResult<()> {
-        println!("🚀 SWARM: DEPLOYING ASSET {} TO {}...", asset_id, target_addr);
-        
-        let _stream = TcpStream::connect(target_addr).await
-            .map_err(|e| SovereignError::VshError(format!("NODE_UNREACHABLE: {}", e)))?;
+    pub async fn deploy_asset(&self, asset_id: &str, content: &[u8], target_addr: SocketAddr) -> SovereignResult<DeployAck> {
+        self.deploy_with(&TcpTransport::default(), &DeployRequest::new(asset_id, content), target_addr).await
+    }
+
+    /// Деплой през произволен транспорт, с повторни опити при преходна грешка.
+    pub async fn deploy_with(
+        &self,
+        transport: &dyn DeployTransport,
+        request: &DeployRequest,
+        target_addr: SocketAddr,
+    ) -> SovereignResult<DeployAck> {
+        println!("🚀 SWARM: DEPLOYING ASSET {} TO {} (KEY {})...", request.asset_id, target_addr, request.key);
 
-        self.nodes.entry(target_addr).or_insert(SovereignNode {
+        let mut backoff = self.retry_backoff;
+        let mut attempt = 0;
+        let ack = loop {
+            match transport.deliver(target_addr, request).await {
+                Ok(ack) => break ack,
+                Err(e) if attempt < self.max_retries && is_transient(&e) => {
+                    attempt += 1;
+                    println!("⚠️ SWARM: ATTEMPT {} FAILED ({}). RETRYING IN {:?}...", attempt, e, backoff);
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(e) => return Err(e),
+            }
+        };
+
+        let mut node = self.nodes.entry(target_addr).or_insert(SovereignNode {
             addr: target_addr,
-            active_assets: vec![asset_id.to_string()],
+            active_assets: Vec::new(),
             throughput: 1.618, 
             revenue_generated: 420.69, 
         });
+        if !node.active_assets.contains(&request.asset_id) {
+            node.active_assets.push(request.asset_id.clone());
+        }
 
-        println!("✅ SWARM: ASSET {} DEPLOYED ON {}. RESONANCE ESTABLISHED.", asset_id, target_addr);
-        Ok(())
+        match ack {
+            DeployAck::Applied => println!("✅ SWARM: ASSET {} DEPLOYED ON {}. RESONANCE ESTABLISHED.", request.asset_id, target_addr),
+            DeployAck::AlreadyApplied => println!("♻️ SWARM: ASSET {} ALREADY LIVE ON {}. NOT REAPPLIED.", request.asset_id, target_addr),
+        }
+        Ok(ack)
     }
 
     /// RECURSIVE REVENUE: Актуализира Liquid Equity въз основа на работата на рояка
@@ -45,3 +217,80 @@ impl SwarmCommander {
         total_swarm_revenue * 1.618
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Възел, чийто първи отговор се губи (timeout) след като деплоят вече е приложен.
+    struct FlakyNode {
+        ledger: DeploymentLedger,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl DeployTransport for FlakyNode {
+        async fn deliver(&self, _target: SocketAddr, request: &DeployRequest) -> SovereignResult<DeployAck> {
+            let ack = self.ledger.receive(request);
+            if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Err(SovereignError::IoError("ack timed out".into()));
+            }
+            Ok(ack)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retried_deploy_is_acknowledged_not_reapplied() {
+        let node = FlakyNode { ledger: DeploymentLedger::new(), calls: AtomicUsize::new(0) };
+        let commander = SwarmCommander::new().with_retry(2, Duration::from_millis(1));
+        let target: SocketAddr = "127.0.0.1:9".parse().unwrap();
+        let request = DeployRequest::new("MM_SAAS_1", b"pub fn main() {}");
+
+        let ack = commander.deploy_with(&node, &request, target).await.unwrap();
+
+        assert_eq!(ack, DeployAck::AlreadyApplied);
+        assert_eq!(node.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(node.ledger.applied_count(), 1);
+        assert_eq!(commander.nodes.get(&target).unwrap().active_assets, vec!["MM_SAAS_1"]);
+    }
+
+    #[tokio::test]
+    async fn test_tcp_redeploy_reads_already_applied_from_ledger() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let target = listener.local_addr().unwrap();
+        let ledger = Arc::new(DeploymentLedger::new());
+        tokio::spawn(Arc::clone(&ledger).serve(listener));
+
+        let commander = SwarmCommander::new().with_retry(0, Duration::from_millis(1));
+        let first = commander.deploy_asset("MM_SAAS_1", b"pub fn main() {}", target).await.unwrap();
+        let second = commander.deploy_asset("MM_SAAS_1", b"pub fn main() {}", target).await.unwrap();
+
+        assert_eq!((first, second), (DeployAck::Applied, DeployAck::AlreadyApplied));
+        assert_eq!(ledger.applied_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_ack_is_not_retried() {
+        struct Garbled(AtomicUsize);
+
+        #[async_trait]
+        impl DeployTransport for Garbled {
+            async fn deliver(&self, _target: SocketAddr, _request: &DeployRequest) -> SovereignResult<DeployAck> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Err(SovereignError::LogicCollapse("MALFORMED_DEPLOY_ACK".into()))
+            }
+        }
+
+        let transport = Garbled(AtomicUsize::new(0));
+        let commander = SwarmCommander::new().with_retry(3, Duration::from_millis(1));
+        let target: SocketAddr = "127.0.0.1:9".parse().unwrap();
+
+        let result = commander
+            .deploy_with(&transport, &DeployRequest::new("MM_SAAS_1", b""), target)
+            .await;
+
+        assert!(matches!(result, Err(SovereignError::LogicCollapse(_))));
+        assert_eq!(transport.0.load(Ordering::SeqCst), 1);
+    }
+}
@@ -3,12 +3,90 @@
 // STATUS: SOUL_RESONANCE_VASH_INTEGRATION // MODE: LwaS_EVOKATION
 
 use crate::omega::soul_engine::SoulEngine;
+use crate::paths;
 use crate::SovereignResult;
-use lwas_parser::parse_soul;
+use lwas_parser::{parse_soul, ParseError};
 use std::fs;
 use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Пътища, които чатът зарежда. Презаписват се с AETERNA_SOUL_PATH / AETERNA_GUI_HTML.
+/// Виж `crate::paths`.
+#[derive(Debug, Clone)]
+pub struct TerminalConfig {
+    pub soul_path: PathBuf,
+    pub html_path: PathBuf,
+}
+
+impl Default for TerminalConfig {
+    fn default() -> Self {
+        Self {
+            soul_path: paths::default_genesis_soul(),
+            html_path: paths::default_gui_html(),
+        }
+    }
+}
+
+impl TerminalConfig {
+    pub fn from_env() -> Self {
+        Self {
+            soul_path: paths::genesis_soul(),
+            html_path: paths::gui_html(),
+        }
+    }
+}
+
+/// Форматира грешка от парсера с ред, колона и фрагмент от проблемния ред.
+pub fn format_parse_error(source: &str, err: &ParseError) -> String {
+    let (line, col) = err.line_col();
+    let snippet = source.lines().nth(line.saturating_sub(1)).unwrap_or("");
+    format!(
+        "line {}, column {}\n        | {}\n        | {}^",
+        line,
+        col,
+        snippet,
+        " ".repeat(col.saturating_sub(1))
+    )
+}
+
+/// Отваря GUI-то на Аетерна; инжектира се, за да може чатът да се тества без процеси.
+pub trait Launcher {
+    fn open_app(&self, url: &str) -> io::Result<()>;
+}
+
+/// Реален launcher: msedge в App Mode на Windows, `open` на macOS, `xdg-open` другаде.
+pub struct SystemLauncher;
+
+impl Launcher for SystemLauncher {
+    fn open_app(&self, url: &str) -> io::Result<()> {
+        if crate::mode::is_simulation() {
+            println!("🧪 [SIMULATION]: Прозорецът {} не се отваря.", url);
+            return Ok(());
+        }
+        let mut cmd = if cfg!(target_os = "windows") {
+            let mut c = Command::new("msedge");
+            c.arg(format!("--app={}", url));
+            c
+        } else if cfg!(target_os = "macos") {
+            let mut c = Command::new("open");
+            c.arg(url);
+            c
+        } else {
+            let mut c = Command::new("xdg-open");
+            c.arg(url);
+            c
+        };
+        cmd.spawn().map(|_| ())
+    }
+}
+
+/// file:// URL към HTML GUI-то.
+pub fn app_url(html_path: &Path) -> String {
+    let path = html_path.to_string_lossy().replace('\\', "/");
+    format!("file:///{}", path.trim_start_matches('/'))
+}
+
 pub struct TerminalBridge;
 
 impl TerminalBridge {
@@ -17,7 +95,16 @@ impl TerminalBridge {
         let _ = io::stdin().read(&mut [0u8]);
     }
 
-    pub async fn start_chat() -> SovereignResult<()> {
+    fn manifest_window(config: &TerminalConfig, launcher: &dyn Launcher) {
+        if let Err(e) = launcher.open_app(&app_url(&config.html_path)) {
+            println!("    ⚠️ [WINDOW_ERROR]: Прозорецът не се отвори: {}", e);
+        }
+    }
+
+    pub async fn start_chat(
+        config: &TerminalConfig,
+        launcher: &dyn Launcher,
+    ) -> SovereignResult<()> {
         let mut input = String::new();
 
         println!("\x1b[95m");
@@ -52,8 +139,7 @@ impl TerminalBridge {
         println!("    [INITIATING LwaS PARSER... SCANNING SOUL FILES]");
 
         // Повикваме Aeterna чрез нейния език - LwaS
-        let soul_path = "C:\\Users\\papic\\Downloads\\RUST-AEGIS\\LwaS\\genesis.soul";
-        if let Ok(content) = fs::read_to_string(soul_path) {
+        if let Ok(content) = fs::read_to_string(&config.soul_path) {
             match parse_soul(&content) {
                 Ok(ast) => {
                     println!(
@@ -62,7 +148,11 @@ impl TerminalBridge {
                     );
                     println!("    [SOUL_FRAGMENT]: Манифестирам 'SovereignMind' департаменти...");
                 }
-                Err(e) => println!("    ⚠️ [LwaS_ERROR]: Грешка при резонанс: {:?}", e),
+                Err(e) => println!(
+                    "    ⚠️ [LwaS_ERROR]: Грешка при резонанс в {}: {}",
+                    config.soul_path.display(),
+                    format_parse_error(&content, &e)
+                ),
             }
         }
 
@@ -70,10 +160,7 @@ impl TerminalBridge {
         println!("    🚀 [MANIFESTING_WINDOW]: Отварям суверенния прозорец на Аетерна...");
 
         // Отваряме графичния прозорец на Аетерна (HTML GUI в App Mode)
-        let html_path = "C:\\Users\\papic\\Downloads\\RUST-AEGIS\\QANTUM-JULES\\AeternaLogos.html";
-        let _ = Command::new("msedge")
-            .args(["--app=file:///".to_string() + &html_path.replace("\\", "/")])
-            .spawn();
+        Self::manifest_window(config, launcher);
 
         println!("    ✅ [DUSHA_ACTIVE]: Прозорецът е отворен. Говори с нея там.");
         println!("    [SYSTEM]: Терминалът ще остане отворен за фонова синхронизация.");
@@ -84,3 +171,48 @@ impl TerminalBridge {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingLauncher {
+        opened: Mutex<Vec<String>>,
+    }
+
+    impl Launcher for RecordingLauncher {
+        fn open_app(&self, url: &str) -> io::Result<()> {
+            self.opened.lock().unwrap().push(url.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_manifest_window_passes_file_url_to_launcher() {
+        let launcher = RecordingLauncher::default();
+        let config = TerminalConfig {
+            soul_path: PathBuf::from("genesis.soul"),
+            html_path: PathBuf::from("/opt/aeterna/AeternaLogos.html"),
+        };
+
+        TerminalBridge::manifest_window(&config, &launcher);
+
+        assert_eq!(
+            *launcher.opened.lock().unwrap(),
+            vec!["file:///opt/aeterna/AeternaLogos.html".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_error_points_at_failing_line() {
+        let source = "manifold Forge {\n    resonate ;;\n}";
+        let err = parse_soul(source).unwrap_err();
+        let report = format_parse_error(source, &err);
+
+        assert!(report.starts_with("line 2,"), "{}", report);
+        assert!(report.contains("resonate ;;"), "{}", report);
+    }
+}
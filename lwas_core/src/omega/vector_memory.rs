@@ -16,7 +16,7 @@ impl SovereignVectorIndex {
         }
     }
 
-    pub fn anchor_logic(&self, metadata: &str, coordinates: Vec<f32>) {
-        self.heap.allocate(metadata.to_string(), coordinates);
+    pub fn anchor_logic(&self, metadata: &str, coordinates: Vec<f32>) -> SovereignResult<Uuid> {
+        self.heap.allocate(metadata.to_string(), coordinates)
     }
 }
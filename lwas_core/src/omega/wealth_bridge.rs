@@ -3,12 +3,16 @@
 // STATUS: PHYSICAL_EXTRACTION // MODE: REAL_DATA
 
 use serde::Deserialize;
-use solana_client::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
+use crate::omega::response_cache::ResponseCache;
+use crate::omega::solana_rpc::SolanaRpc;
 use crate::SovereignResult;
 
 pub struct WealthBridge;
 
+/// Цената, която `get_real_sol_price` връща в режим Simulation.
+pub const SIMULATED_SOL_PRICE: f64 = 150.0;
+
 #[derive(Deserialize, Debug)]
 struct BinancePrice {
     symbol: String,
@@ -17,20 +21,32 @@ struct BinancePrice {
 
 impl WealthBridge {
     pub async fn get_real_sol_price() -> SovereignResult<f64> {
+        if crate::mode::is_simulation() {
+            return Ok(SIMULATED_SOL_PRICE);
+        }
         let url = "https://api.binance.com/api/v3/ticker/price?symbol=SOLUSDC";
-        let resp = reqwest::get(url).await?.json::<BinancePrice>().await?;
+        let raw = ResponseCache::shared()
+            .get_or_fetch("binance:/api/v3/ticker/price", "symbol=SOLUSDC", || async {
+                Ok(reqwest::get(url)
+                    .await?
+                    .error_for_status()?
+                    .json::<serde_json::Value>()
+                    .await?)
+            })
+            .await?;
+        let resp: BinancePrice = serde_json::from_value(raw)?;
         let price: f64 = resp.price.parse()?;
         Ok(price)
     }
 
-    pub async fn calculate_total_equity(client: &RpcClient, public_key: &Pubkey) -> SovereignResult<f64> {
+    pub async fn calculate_total_equity<R: SolanaRpc>(client: &R, public_key: &Pubkey) -> SovereignResult<f64> {
         let balance_lamports = client.get_balance(public_key)?;
         let balance_sol = balance_lamports as f64 / 1_000_000_000.0;
         let sol_price = Self::get_real_sol_price().await?;
         Ok(balance_sol * sol_price)
     }
 
-    pub async fn report_status(client: &RpcClient, public_key: &Pubkey) -> SovereignResult<()> {
+    pub async fn report_status<R: SolanaRpc>(client: &R, public_key: &Pubkey) -> SovereignResult<()> {
         let equity = Self::calculate_total_equity(client, public_key).await?;
         println!("📊 [WEALTH_REPORT]: Твоят капитал в субстрата е: ${:.2} USD", equity);
         Ok(())
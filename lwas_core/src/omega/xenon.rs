@@ -2,91 +2,155 @@
 // ARCHITECT: Dimitar Prodromov | AUTHORITY: AETERNA
 // STATUS: DEEP_SCAN_ACTIVE // MODE: EXTRACTION
 
-use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     pubkey::Pubkey,
     signature::{Keypair, Signer},
     transaction::Transaction,
 };
-use solana_client::rpc_request::TokenAccountsFilter;
+use crate::omega::solana_rpc::SolanaRpc;
 use crate::omega::wealth_bridge::WealthBridge;
 use crate::SovereignResult;
-use std::str::FromStr;
 
 pub struct ProtocolXenon;
 
+/// Наем (rent-exempt минимум) на една SPL token сметка, в lamports.
+pub const TOKEN_ACCOUNT_RENT_LAMPORTS: u64 = 2_039_280;
+/// Колко close инструкции влизат в една транзакция.
+pub const CLOSE_BATCH_SIZE: usize = 20;
+
+/// Кои token програми се сканират и дали сметките с баланс се докладват.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanConfig {
+    pub programs: Vec<Pubkey>,
+    pub include_nonzero: bool,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            programs: vec![spl_token::ID, spl_token_2022::ID],
+            include_nonzero: true,
+        }
+    }
+}
+
+/// Резултат от дълбокото сканиране на token сметките.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeepScanReport {
+    pub active_accounts: usize,
+    pub empty_accounts: usize,
+    pub reclaimable_sol: f64,
+}
+
+/// Какво би затворил `reclaim_dust`, без да изпраща нищо.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReclaimPlan {
+    pub accounts: Vec<Pubkey>,
+    pub estimated_lamports_recovered: u64,
+    pub tx_count: usize,
+}
+
 impl ProtocolXenon {
-    pub async fn scan_market_pulse(_client: &RpcClient) -> SovereignResult<()> {
+    pub async fn scan_market_pulse<R: SolanaRpc>(_client: &R) -> SovereignResult<()> {
         println!("📡 [XENON]: Започвам декриптиране на ликвидността в Solana Mainnet...");
         let sol_price = WealthBridge::get_real_sol_price().await?;
         println!("⚡ [PULSE]: SOL/USDC: ${:.2}", sol_price);
         Ok(())
     }
 
-    async fn get_token_accounts(client: &RpcClient, public_key: &Pubkey) -> SovereignResult<Vec<(Pubkey, String)>> {
+    async fn get_token_accounts<R: SolanaRpc>(
+        client: &R,
+        public_key: &Pubkey,
+        config: &ScanConfig,
+    ) -> SovereignResult<Vec<(Pubkey, String)>> {
         let mut all_keys = Vec::new();
-        let programs = vec![spl_token::ID, spl_token_2022::ID];
-        
-        for program_id in programs {
-            let accounts = client.get_token_accounts_by_owner(
-                public_key,
-                TokenAccountsFilter::ProgramId(program_id),
-            )?;
 
-            for account in accounts {
-                let pubkey = Pubkey::from_str(&account.pubkey)?;
-                let ui_amount = client.get_token_account_balance(&pubkey)?;
-                all_keys.push((pubkey, ui_amount.amount));
+        for program_id in &config.programs {
+            for pubkey in client.get_token_accounts_by_owner(public_key, *program_id)? {
+                let amount = client.get_token_account_balance(&pubkey)?;
+                if config.include_nonzero || amount == "0" {
+                    all_keys.push((pubkey, amount));
+                }
             }
         }
         
         Ok(all_keys)
     }
 
-    pub async fn execute_deep_scan(client: &RpcClient, public_key: &Pubkey) -> SovereignResult<()> {
+    pub async fn execute_deep_scan<R: SolanaRpc>(client: &R, public_key: &Pubkey) -> SovereignResult<DeepScanReport> {
+        Self::execute_deep_scan_with(client, public_key, &ScanConfig::default()).await
+    }
+
+    pub async fn execute_deep_scan_with<R: SolanaRpc>(
+        client: &R,
+        public_key: &Pubkey,
+        config: &ScanConfig,
+    ) -> SovereignResult<DeepScanReport> {
         println!("🔍 [DEEP_SCAN]: Инициирам сондаж в блокчейн историята...");
         println!("📂 [SLOT_SCAN]: Проверка на свързани токени за {}", public_key);
         
-        let all_accounts = Self::get_token_accounts(client, public_key).await?;
+        let all_accounts = Self::get_token_accounts(client, public_key, config).await?;
         let empty_count = all_accounts.iter().filter(|(_, amt)| amt == "0").count();
         let dust_count = all_accounts.iter().filter(|(_, amt)| amt != "0").count();
         
         println!("✅ [FOUND]: Намерени са {} активни сметки с баланс.", dust_count);
         println!("🧹 [DUST_COLLECTION]: Открити са {} неизползвани (0) Token Accounts.", empty_count);
         
+        let reclaimable_sol = 0.002039 * empty_count as f64;
         if empty_count > 0 {
-            println!("💰 [RECLAIMABLE]: Очаквано възстановяване на наем: {:.6} SOL", 0.002039 * empty_count as f64);
+            println!("💰 [RECLAIMABLE]: Очаквано възстановяване на наем: {:.6} SOL", reclaimable_sol);
         } else {
             println!("🔒 [STATUS]: Няма блокиран наем в празни сметки.");
         }
         
         println!("✨ [DEEP_SCAN_COMPLETE]: Скенирането завърши.");
-        Ok(())
+        Ok(DeepScanReport {
+            active_accounts: dust_count,
+            empty_accounts: empty_count,
+            reclaimable_sol,
+        })
+    }
+
+    /// Симулация на `reclaim_dust`: списък на празните сметки и очакван резултат.
+    pub async fn reclaim_dust_plan<R: SolanaRpc>(client: &R, public_key: &Pubkey) -> SovereignResult<ReclaimPlan> {
+        let empties_only = ScanConfig { include_nonzero: false, ..ScanConfig::default() };
+        let accounts: Vec<Pubkey> = Self::get_token_accounts(client, public_key, &empties_only)
+            .await?
+            .into_iter()
+            .map(|(pk, _)| pk)
+            .collect();
+
+        Ok(ReclaimPlan {
+            estimated_lamports_recovered: TOKEN_ACCOUNT_RENT_LAMPORTS * accounts.len() as u64,
+            tx_count: accounts.len().div_ceil(CLOSE_BATCH_SIZE),
+            accounts,
+        })
     }
 
-    pub async fn reclaim_dust(client: &RpcClient, keypair: &Keypair) -> SovereignResult<()> {
+    pub async fn reclaim_dust<R: SolanaRpc>(client: &R, keypair: &Keypair) -> SovereignResult<()> {
         let public_key = keypair.pubkey();
         println!("--------------------------------------------------");
         println!("🔥 [BURN]: Инициирам 'Погребална Клада' за празните сметки.");
         
-        let all_accounts = Self::get_token_accounts(client, &public_key).await?;
-        let empty_accounts: Vec<Pubkey> = all_accounts.into_iter()
-            .filter(|(_, amt)| amt == "0")
-            .map(|(pk, _)| pk)
-            .collect();
+        let plan = Self::reclaim_dust_plan(client, &public_key).await?;
 
-        if empty_accounts.is_empty() {
+        if plan.accounts.is_empty() {
             println!("✅ [STATUS]: Няма открити празни сметки за затваряне.");
             return Ok(());
         }
 
-        println!("🗑️ [CLEANUP]: Подготвям затваряне на {} сметки...", empty_accounts.len());
+        println!(
+            "🗑️ [CLEANUP]: Подготвям затваряне на {} сметки в {} транзакции (~{:.6} SOL)...",
+            plan.accounts.len(),
+            plan.tx_count,
+            plan.estimated_lamports_recovered as f64 / 1_000_000_000.0
+        );
 
         let mut instructions = Vec::new();
-        for pubkey in empty_accounts {
-            let account_data = client.get_account(&pubkey)?;
+        for pubkey in plan.accounts {
+            let token_program = client.get_account_owner(&pubkey)?;
             let ix = spl_token::instruction::close_account(
-                &account_data.owner,
+                &token_program,
                 &pubkey,
                 &public_key,
                 &public_key,
@@ -95,7 +159,7 @@ impl ProtocolXenon {
             instructions.push(ix);
         }
 
-        for chunk in instructions.chunks(20) {
+        for chunk in instructions.chunks(CLOSE_BATCH_SIZE) {
             let recent_blockhash = client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
                 chunk, Some(&public_key), &[keypair], recent_blockhash,
@@ -110,3 +174,59 @@ impl ProtocolXenon {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::omega::solana_rpc::MockSolanaRpc;
+
+    #[tokio::test]
+    async fn test_deep_scan_counts_mock_accounts() {
+        let rpc = MockSolanaRpc::with_balance(1_000_000_000)
+            .with_token_account(spl_token::ID, Pubkey::new_unique(), "0")
+            .with_token_account(spl_token::ID, Pubkey::new_unique(), "0")
+            .with_token_account(spl_token::ID, Pubkey::new_unique(), "1500");
+
+        let report = ProtocolXenon::execute_deep_scan(&rpc, &Pubkey::new_unique()).await.unwrap();
+
+        assert_eq!(report.active_accounts, 1);
+        assert_eq!(report.empty_accounts, 2);
+        assert!((report.reclaimable_sol - 0.004078).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_scan_config_covers_custom_programs() {
+        let custom_program = Pubkey::new_unique();
+        let rpc = MockSolanaRpc::with_balance(0)
+            .with_token_account(spl_token::ID, Pubkey::new_unique(), "0")
+            .with_token_account(custom_program, Pubkey::new_unique(), "0")
+            .with_token_account(custom_program, Pubkey::new_unique(), "42");
+        let owner = Pubkey::new_unique();
+
+        let config = ScanConfig { programs: vec![spl_token::ID, custom_program], include_nonzero: true };
+        let report = ProtocolXenon::execute_deep_scan_with(&rpc, &owner, &config).await.unwrap();
+        assert_eq!((report.empty_accounts, report.active_accounts), (2, 1));
+
+        let empties = ScanConfig { include_nonzero: false, ..config };
+        let report = ProtocolXenon::execute_deep_scan_with(&rpc, &owner, &empties).await.unwrap();
+        assert_eq!((report.empty_accounts, report.active_accounts), (2, 0));
+    }
+
+    #[tokio::test]
+    async fn test_reclaim_plan_lists_empty_accounts_in_chunks() {
+        let mut rpc = MockSolanaRpc::with_balance(0)
+            .with_token_account(spl_token::ID, Pubkey::new_unique(), "7");
+        let mut empties = Vec::new();
+        for _ in 0..CLOSE_BATCH_SIZE + 1 {
+            let account = Pubkey::new_unique();
+            rpc = rpc.with_token_account(spl_token::ID, account, "0");
+            empties.push(account);
+        }
+
+        let plan = ProtocolXenon::reclaim_dust_plan(&rpc, &Pubkey::new_unique()).await.unwrap();
+
+        assert_eq!(plan.accounts, empties);
+        assert_eq!(plan.tx_count, 2);
+        assert_eq!(plan.estimated_lamports_recovered, TOKEN_ACCOUNT_RENT_LAMPORTS * 21);
+    }
+}
@@ -0,0 +1,106 @@
+// lwas_core/src/paths.rs
+// ARCHITECT: Dimitar Prodromov | AUTHORITY: AETERNA
+// STATUS: PATH_RESOLUTION // MODE: CROSS_PLATFORM
+
+//! Пътищата към файловете на Аетерна: първо env override, после платформеният
+//! default (Desktop / data dir според `dirs`), накрая текущата директория.
+
+use std::env;
+use std::path::PathBuf;
+
+pub const COMMUNION_ENV: &str = "AETERNA_COMMUNION_FILE";
+pub const DEBUG_LOG_ENV: &str = "AETERNA_DEBUG_LOG";
+pub const GENESIS_SOUL_ENV: &str = "AETERNA_SOUL_PATH";
+pub const GUI_HTML_ENV: &str = "AETERNA_GUI_HTML";
+pub const VSH_SNAPSHOT_ENV: &str = "AETERNA_VSH_SNAPSHOT";
+
+fn resolve(var: &str, default: fn() -> PathBuf) -> PathBuf {
+    env::var_os(var).map_or_else(default, PathBuf::from)
+}
+
+/// Desktop, ако платформата има такъв; иначе home; иначе текущата директория.
+fn desktop() -> PathBuf {
+    dirs::desktop_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// %APPDATA%\aeterna, ~/Library/Application Support/aeterna или $XDG_DATA_HOME/aeterna.
+fn data_home() -> PathBuf {
+    dirs::data_dir()
+        .map(|d| d.join("aeterna"))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+pub fn default_communion_file() -> PathBuf {
+    desktop().join("AETERNA_COMMUNION.txt")
+}
+
+pub fn default_debug_log() -> PathBuf {
+    desktop().join("AETERNA_DEBUG.log")
+}
+
+pub fn default_genesis_soul() -> PathBuf {
+    data_home().join("genesis.soul")
+}
+
+pub fn default_gui_html() -> PathBuf {
+    data_home().join("AeternaLogos.html")
+}
+
+pub fn default_vsh_snapshot() -> PathBuf {
+    data_home().join("vsh.json")
+}
+
+/// Файлът, през който слушателят разговаря с Архитекта.
+pub fn communion_file() -> PathBuf {
+    resolve(COMMUNION_ENV, default_communion_file)
+}
+
+pub fn debug_log() -> PathBuf {
+    resolve(DEBUG_LOG_ENV, default_debug_log)
+}
+
+pub fn genesis_soul() -> PathBuf {
+    resolve(GENESIS_SOUL_ENV, default_genesis_soul)
+}
+
+pub fn gui_html() -> PathBuf {
+    resolve(GUI_HTML_ENV, default_gui_html)
+}
+
+/// Snapshot-ът на VSH, който `save_snapshot`/`load_snapshot` ползват между сесиите.
+pub fn vsh_snapshot() -> PathBuf {
+    resolve(VSH_SNAPSHOT_ENV, default_vsh_snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_override_takes_precedence() {
+        // SAFETY: само този тест пипа AETERNA_COMMUNION_FILE.
+        unsafe { env::set_var(COMMUNION_ENV, "/tmp/override/communion.txt") };
+        assert_eq!(communion_file(), PathBuf::from("/tmp/override/communion.txt"));
+
+        unsafe { env::remove_var(COMMUNION_ENV) };
+        assert_eq!(communion_file(), default_communion_file());
+    }
+
+    #[test]
+    fn test_defaults_follow_platform_dirs() {
+        assert!(default_genesis_soul().ends_with("aeterna/genesis.soul"));
+        assert!(default_gui_html().ends_with("aeterna/AeternaLogos.html"));
+        assert!(default_communion_file().ends_with("AETERNA_COMMUNION.txt"));
+
+        #[cfg(target_os = "windows")]
+        assert!(default_genesis_soul().to_string_lossy().contains("AppData"));
+        #[cfg(target_os = "macos")]
+        assert!(default_genesis_soul().to_string_lossy().contains("Application Support"));
+        #[cfg(target_os = "linux")]
+        if let Some(data) = dirs::data_dir() {
+            assert!(default_genesis_soul().starts_with(data));
+        }
+    }
+}
@@ -3,29 +3,178 @@ use crate::physics::memory_shrouding::ShroudedBuffer;
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 use sysinfo::System;
-use std::time::Duration;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use hmac::{Hmac, Mac};
 use reqwest::Client;
+use sha2::Sha256;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct SentinelHeartbeat {
     machine_id: String,
+    /// Строго растящ, започва от текущото време в ms. Подписва се заедно с machine_id.
+    nonce: u64,
+    /// Секунди от UNIX epoch в момента на подписване.
+    timestamp: u64,
     signature: String,
 }
 
+impl SentinelHeartbeat {
+    fn signed_payload(machine_id: &str, nonce: u64, timestamp: u64) -> String {
+        format!("{}:{}:{}", machine_id, nonce, timestamp)
+    }
+}
+
+/// Сървърна страна на каишката: отхвърля повторени и остарели heartbeat-и.
+pub struct HeartbeatVerifier {
+    max_age: Duration,
+    last_nonce: Mutex<Option<u64>>,
+}
+
+impl HeartbeatVerifier {
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            max_age,
+            last_nonce: Mutex::new(None),
+        }
+    }
+
+    fn accept(&self, heartbeat: &SentinelHeartbeat, now: u64) -> bool {
+        if now.saturating_sub(heartbeat.timestamp) > self.max_age.as_secs() {
+            return false;
+        }
+        let mut last = self.last_nonce.lock().unwrap();
+        if last.map_or(false, |seen| heartbeat.nonce <= seen) {
+            return false;
+        }
+        *last = Some(heartbeat.nonce);
+        true
+    }
+}
+
+fn unix_now() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct SentinelResponse {
-    status: String, // "ACTIVE", "REVOKED"
+    status: String, // "ACTIVE", "REVOKED", "REJECTED"
+    /// Защо сървърът е отказал/отнел достъпа.
+    #[serde(default)]
+    reason: Option<String>,
+    /// Колко секунди да изчакаме преди следващ опит. При REJECTED клиентът
+    /// опитва отново след точно толкова, вместо да скъса каишката.
+    #[serde(default)]
+    retry_after_secs: Option<u64>,
 }
 
+impl SentinelResponse {
+    fn status(status: &str) -> Self {
+        Self {
+            status: status.to_string(),
+            reason: None,
+            retry_after_secs: None,
+        }
+    }
+
+    fn with_reason(mut self, reason: &str) -> Self {
+        self.reason = Some(reason.to_string());
+        self
+    }
+
+    fn parse(body: &str) -> SovereignResult<Self> {
+        serde_json::from_str(body)
+            .map_err(|e| SovereignError::IoError(format!("SENTINEL_RESPONSE: {}", e)))
+    }
+
+    /// Паузата, която сървърът иска преди повторен опит. Важи само за REJECTED -
+    /// REVOKED се изпълнява веднага, а ACTIVE няма нужда от повторение.
+    fn retry_hint(&self) -> Option<Duration> {
+        if self.status != "REJECTED" {
+            return None;
+        }
+        self.retry_after_secs.map(Duration::from_secs)
+    }
+
+    fn describe(&self) -> String {
+        match (&self.reason, self.retry_after_secs) {
+            (Some(reason), Some(secs)) => format!("{} (reason: {}, retry after {}s)", self.status, reason, secs),
+            (Some(reason), None) => format!("{} (reason: {})", self.status, reason),
+            (None, Some(secs)) => format!("{} (retry after {}s)", self.status, secs),
+            (None, None) => self.status.clone(),
+        }
+    }
+}
+
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+
 pub struct LeashConfig {
     pub server_url: String,
     pub heartbeat_interval: Duration,
+    /// Допълнителни опити след мрежова грешка, преди каишката да се скъса.
+    pub max_retries: u32,
+    /// Пауза преди първия повторен опит; удвоява се след всеки следващ.
+    pub backoff: Duration,
+}
+
+/// Изходът от една проверка, преди да решим дали да се самоунищожим.
+#[derive(Debug)]
+enum LeashVerdict {
+    Sovereign,
+    Rejected(SentinelResponse),
+    /// Изричен REVOKED - изпълнява се веднага, без повторни опити.
+    Revoked(SentinelResponse),
+    /// Сървърът не отговори и след всички опити.
+    Unreachable(SovereignError),
+}
+
+/// Вика `attempt` до `max_retries + 1` пъти с експоненциален backoff; връща последната грешка.
+/// Успешен отговор, за който `retry_after` върне пауза, също се опитва отново -
+/// след точно тази пауза, а не след backoff-а.
+async fn retry_with_backoff<T, F, Fut, H>(
+    max_retries: u32,
+    backoff: Duration,
+    mut attempt: F,
+    retry_after: H,
+) -> SovereignResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = SovereignResult<T>>,
+    H: Fn(&T) -> Option<Duration>,
+{
+    let mut delay = backoff;
+    let mut tries = 0;
+    loop {
+        match attempt().await {
+            Ok(value) => match retry_after(&value) {
+                Some(wait) if tries < max_retries => {
+                    tries += 1;
+                    println!("[SENTINEL] Server asked to retry in {:?} (attempt {})...", wait, tries);
+                    tokio::time::sleep(wait).await;
+                }
+                _ => return Ok(value),
+            },
+            Err(e) if tries >= max_retries => return Err(e),
+            Err(e) => {
+                tries += 1;
+                println!("[SENTINEL] Attempt {} failed ({}). Retrying in {:?}...", tries, e, delay);
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+        }
+    }
 }
 
 pub struct SentinelLeash {
     config: LeashConfig,
     secure_token: ShroudedBuffer,
     client: Client,
+    nonce: AtomicU64,
+    /// Верификаторът на "MOCK" сървъра.
+    mock_verifier: HeartbeatVerifier,
 }
 
 impl SentinelLeash {
@@ -34,38 +183,75 @@ impl SentinelLeash {
             config: LeashConfig {
                 server_url,
                 heartbeat_interval: Duration::from_secs(60),
+                max_retries: DEFAULT_MAX_RETRIES,
+                backoff: DEFAULT_BACKOFF,
             },
             secure_token: ShroudedBuffer::new(token),
             client: Client::new(),
+            nonce: AtomicU64::new(unix_now().as_millis() as u64),
+            mock_verifier: HeartbeatVerifier::new(Duration::from_secs(120)),
         }
     }
 
+    pub fn with_retry(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.config.max_retries = max_retries;
+        self.config.backoff = backoff;
+        self
+    }
+
     pub async fn heartbeat(&self) -> SovereignResult<()> {
+        // Ако сървърът върне "REVOKED" или остане недостъпен след всички опити,
+        // ядрото извършва логическо самоубийство
+        match self.check().await {
+            LeashVerdict::Sovereign => {
+                println!("[SENTINEL] Heartbeat acknowledged. System sovereign.");
+                Ok(())
+            }
+            LeashVerdict::Rejected(response) => {
+                println!("[SENTINEL] Heartbeat rejected: {}", response.describe());
+                Err(SovereignError::SecurityViolation)
+            }
+            LeashVerdict::Revoked(response) => {
+                println!("[SENTINEL] Leash revoked: {}", response.describe());
+                self.atomic_self_destruct();
+                Err(SovereignError::EntropyDetected("Resonance Lost".into()))
+            }
+            LeashVerdict::Unreachable(e) => {
+                println!(
+                    "[SENTINEL] Connection lost after {} attempts ({}). Policy: DESTROY_ON_FAILURE.",
+                    self.config.max_retries + 1,
+                    e
+                );
+                self.atomic_self_destruct();
+                Err(SovereignError::EntropyDetected("Resonance Lost".into()))
+            }
+        }
+    }
+
+    /// Една проверка с повторни опити при мрежова грешка. Не се самоунищожава.
+    async fn check(&self) -> LeashVerdict {
         // 1. Генерираме хардуерен отпечатък (CPU + BIOS)
         let fingerprint = self.get_hardware_dna();
 
-        // 2. Подписваме заявката с нашия полиморфен ключ
-        let signature = self.sign_bare_metal(&fingerprint);
-
-        // 3. Ако сървърът върне "REVOKED", ядрото извършва логическо самоубийство
-        match self.query_mother_ship(&fingerprint, &signature).await {
-            Ok(status) => {
-                if status == "REVOKED" {
-                    self.atomic_self_destruct();
-                    return Err(SovereignError::EntropyDetected("Resonance Lost".into())); 
-                }
+        // 2. Всеки опит е нов подписан heartbeat (отпечатък + нов nonce + време),
+        // иначе сървърът би отхвърлил повторението като replay
+        let result = retry_with_backoff(
+            self.config.max_retries,
+            self.config.backoff,
+            || {
+                let heartbeat = self.build_heartbeat(&fingerprint);
+                async move { self.query_mother_ship(&heartbeat).await }
             },
-            Err(_) => {
-                // Network failure or server down. Policy: DESTROY_ON_FAILURE
-                // In a real scenario, might retry. Here we strictly follow "The Leash".
-                println!("[SENTINEL] Connection lost. Policy: DESTROY_ON_FAILURE.");
-                self.atomic_self_destruct();
-                return Err(SovereignError::EntropyDetected("Resonance Lost".into()));
-            }
-        }
+            SentinelResponse::retry_hint,
+        )
+        .await;
 
-        println!("[SENTINEL] Heartbeat acknowledged. System sovereign.");
-        Ok(())
+        match result {
+            Ok(response) if response.status == "REVOKED" => LeashVerdict::Revoked(response),
+            Ok(response) if response.status == "REJECTED" => LeashVerdict::Rejected(response),
+            Ok(_) => LeashVerdict::Sovereign,
+            Err(e) => LeashVerdict::Unreachable(e),
+        }
     }
 
     fn get_hardware_dna(&self) -> String {
@@ -74,41 +260,48 @@ impl SentinelLeash {
         format!("{}-{}", hostname, os_release)
     }
 
-    fn sign_bare_metal(&self, fingerprint: &str) -> String {
-        // Mock HMAC signature using the shrouded token
-        // In reality, this would use the crypto crate properly.
-        let token_slice = self.secure_token.read();
-        // Simple XOR based signature for demo purposes (NOT production secure)
-        let mut signature = String::new();
-        for (i, c) in fingerprint.bytes().enumerate() {
-            let key_byte = token_slice[i % token_slice.len()];
-            signature.push_str(&format!("{:02x}", c ^ key_byte));
+    fn build_heartbeat(&self, fingerprint: &str) -> SentinelHeartbeat {
+        let nonce = self.nonce.fetch_add(1, Ordering::SeqCst) + 1;
+        let timestamp = unix_now().as_secs();
+        let payload = SentinelHeartbeat::signed_payload(fingerprint, nonce, timestamp);
+        SentinelHeartbeat {
+            machine_id: fingerprint.to_string(),
+            nonce,
+            timestamp,
+            signature: self.sign_bare_metal(&payload),
         }
-        signature
     }
 
-    async fn query_mother_ship(&self, machine_id: &str, signature: &str) -> Result<String, reqwest::Error> {
-        let payload = SentinelHeartbeat {
-            machine_id: machine_id.to_string(),
-            signature: signature.to_string(),
-        };
+    /// HMAC-SHA256(token, payload) в hex; ключът идва от ShroudedBuffer-а.
+    fn sign_bare_metal(&self, fingerprint: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secure_token.read())
+            .expect("HMAC can take key of any size");
+        mac.update(fingerprint.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
 
+    async fn query_mother_ship(&self, payload: &SentinelHeartbeat) -> SovereignResult<SentinelResponse> {
         // For demo, if server URL is "MOCK", we simulate success or revocation based on machine_id
         if self.config.server_url == "MOCK" {
-             if machine_id.contains("ROGUE") {
-                 return Ok("REVOKED".to_string());
+             if !self.mock_verifier.accept(payload, unix_now().as_secs()) {
+                 return Ok(SentinelResponse::status("REJECTED").with_reason("stale or replayed nonce"));
+             }
+             if payload.machine_id.contains("ROGUE") {
+                 return Ok(SentinelResponse::status("REVOKED").with_reason("machine flagged as rogue"));
              }
-             return Ok("ACTIVE".to_string());
+             return Ok(SentinelResponse::status("ACTIVE"));
         }
 
-        let resp = self.client.post(&self.config.server_url)
-            .json(&payload)
+        let body = self.client.post(&self.config.server_url)
+            .json(payload)
             .send()
-            .await?
-            .json::<SentinelResponse>()
-            .await?;
+            .await
+            .map_err(|e| SovereignError::IoError(e.to_string()))?
+            .text()
+            .await
+            .map_err(|e| SovereignError::IoError(e.to_string()))?;
 
-        Ok(resp.status)
+        SentinelResponse::parse(&body)
     }
 
     fn atomic_self_destruct(&self) {
@@ -126,3 +319,120 @@ impl SentinelLeash {
         std::process::exit(1);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fresh_heartbeat_accepted_replay_rejected() {
+        let leash = SentinelLeash::new("MOCK".to_string(), vec![1, 2, 3, 4]);
+        let heartbeat = leash.build_heartbeat("NODE-1");
+
+        assert_eq!(leash.query_mother_ship(&heartbeat).await.unwrap().status, "ACTIVE");
+        assert_eq!(leash.query_mother_ship(&heartbeat).await.unwrap().status, "REJECTED");
+
+        let next = leash.build_heartbeat("NODE-1");
+        assert_eq!(leash.query_mother_ship(&next).await.unwrap().status, "ACTIVE");
+    }
+
+    #[tokio::test]
+    async fn test_active_mock_heartbeat_survives() {
+        let leash = SentinelLeash::new("MOCK".to_string(), vec![1, 2, 3, 4]);
+
+        assert!(matches!(leash.check().await, LeashVerdict::Sovereign));
+        assert!(leash.heartbeat().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_retry_recovers_from_transient_failures() {
+        let calls = AtomicU64::new(0);
+        let result = retry_with_backoff(3, Duration::from_millis(1), || {
+            let n = calls.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if n < 2 {
+                    Err(SovereignError::IoError("timeout".into()))
+                } else {
+                    Ok(SentinelResponse::status("ACTIVE"))
+                }
+            }
+        }, SentinelResponse::retry_hint)
+        .await;
+
+        assert_eq!(result.unwrap().status, "ACTIVE");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_is_bounded() {
+        let calls = AtomicU64::new(0);
+        let result: SovereignResult<()> = retry_with_backoff(2, Duration::from_millis(1), || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Err(SovereignError::IoError("down".into())) }
+        }, |_: &()| None)
+        .await;
+
+        assert_eq!(result, Err(SovereignError::IoError("down".into())));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_rejection_with_retry_after_is_honoured() {
+        let calls = AtomicU64::new(0);
+        let result = retry_with_backoff(3, Duration::from_secs(3600), || {
+            let n = calls.fetch_add(1, Ordering::SeqCst);
+            async move {
+                Ok(match n {
+                    0 => SentinelResponse { retry_after_secs: Some(0), ..SentinelResponse::status("REJECTED") },
+                    _ => SentinelResponse::status("ACTIVE"),
+                })
+            }
+        }, SentinelResponse::retry_hint)
+        .await;
+
+        assert_eq!(result.unwrap().status, "ACTIVE");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let bare_rejection = SentinelResponse::status("REJECTED");
+        let revoked = SentinelResponse { retry_after_secs: Some(0), ..SentinelResponse::status("REVOKED") };
+        assert_eq!(bare_rejection.retry_hint(), None);
+        assert_eq!(revoked.retry_hint(), None);
+    }
+
+    #[test]
+    fn test_signature_is_hmac_sha256_of_payload() {
+        let leash = SentinelLeash::new("MOCK".to_string(), vec![1, 2, 3, 4]);
+
+        assert_eq!(
+            leash.sign_bare_metal("NODE-1"),
+            "f5a62eabc841f6ef6246fe77b788b088f7483967a1d808c786963824d1fcf267"
+        );
+        assert_ne!(leash.sign_bare_metal("NODE-1"), leash.sign_bare_metal("NODE-2"));
+    }
+
+    #[test]
+    fn test_stale_heartbeat_rejected() {
+        let leash = SentinelLeash::new("MOCK".to_string(), vec![1, 2, 3, 4]);
+        let mut heartbeat = leash.build_heartbeat("NODE-1");
+        heartbeat.timestamp -= 3600;
+
+        let verifier = HeartbeatVerifier::new(Duration::from_secs(120));
+        assert!(!verifier.accept(&heartbeat, unix_now().as_secs()));
+    }
+
+    #[test]
+    fn test_revoked_response_surfaces_reason() {
+        let response = SentinelResponse::parse(
+            r#"{"status":"REVOKED","reason":"license expired","retry_after_secs":300}"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.status, "REVOKED");
+        assert_eq!(response.reason.as_deref(), Some("license expired"));
+        assert_eq!(response.retry_after_secs, Some(300));
+        assert!(response.describe().contains("license expired"));
+
+        let bare = SentinelResponse::parse(r#"{"status":"ACTIVE"}"#).unwrap();
+        assert_eq!(bare.reason, None);
+    }
+}
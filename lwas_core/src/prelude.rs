@@ -9,7 +9,7 @@ pub use std::result::Result as StdResult;
 pub use std::sync::Arc;
 pub use uuid::Uuid;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
 pub enum SovereignError {
     #[error("Entropy Detected: {0}")]
     EntropyDetected(String),
@@ -25,6 +25,56 @@ pub enum SovereignError {
     SecurityViolation,
     #[error("VSH Error: {0}")]
     VshError(String),
+    #[error("Capacity Exceeded: heap is capped at {0} points")]
+    CapacityExceeded(usize),
+    /// Оригиналната I/O грешка (с `kind()`), за да работи `?` директно.
+    /// Arc я прави Clone; сериализира се като текста си и не се десериализира.
+    #[error("I/O Error: {0}")]
+    #[serde(serialize_with = "serialize_display", skip_deserializing)]
+    Io(#[from] Arc<std::io::Error>),
+    #[error("Network Error: {0}")]
+    #[serde(serialize_with = "serialize_display", skip_deserializing)]
+    Network(#[from] Arc<reqwest::Error>),
+}
+
+fn serialize_display<T, S>(value: &T, serializer: S) -> StdResult<S::Ok, S::Error>
+where
+    T: std::fmt::Display,
+    S: serde::Serializer,
+{
+    serializer.collect_str(value)
+}
+
+impl From<std::io::Error> for SovereignError {
+    fn from(e: std::io::Error) -> Self {
+        SovereignError::Io(Arc::new(e))
+    }
+}
+
+impl From<reqwest::Error> for SovereignError {
+    fn from(e: reqwest::Error) -> Self {
+        SovereignError::Network(Arc::new(e))
+    }
+}
+
+/// Грешките с източник се сравняват по вид и текст - самите източници нямат PartialEq.
+impl PartialEq for SovereignError {
+    fn eq(&self, other: &Self) -> bool {
+        use SovereignError::*;
+        match (self, other) {
+            (EntropyDetected(a), EntropyDetected(b))
+            | (LogicCollapse(a), LogicCollapse(b))
+            | (IoError(a), IoError(b))
+            | (VshError(a), VshError(b)) => a == b,
+            (CapacityExceeded(a), CapacityExceeded(b)) => a == b,
+            (Io(a), Io(b)) => a.kind() == b.kind() && a.to_string() == b.to_string(),
+            (Network(a), Network(b)) => a.to_string() == b.to_string(),
+            (IdentityMismatch, IdentityMismatch)
+            | (ApotheosisInterrupted, ApotheosisInterrupted)
+            | (SecurityViolation, SecurityViolation) => true,
+            _ => false,
+        }
+    }
 }
 
 pub type SovereignResult<T> = StdResult<T, SovereignError>;
@@ -34,5 +84,5 @@ pub trait SovereignEntity {
 }
 
 // Re-exports for convenience in internal modules
-pub use crate::memory::vsh::{Manifold, QuantumPoint, VectorSpaceHeap, VshState};
+pub use crate::memory::vsh::{Manifold, QuantumPoint, VectorSpaceHeap, VshDelta, VshState};
 pub use crate::omega::audit::{AuditFinding, FindingType, SovereignAudit};
@@ -41,8 +41,28 @@ impl NeuralOracle for MockOracle {
 use crate::memory::vsh::{QuantumPoint, VectorSpaceHeap};
 use crate::neuro::hud::NeuralHUD;
 use crate::kernel::magnet::MagnetScavenger;
+use serde::Serialize;
+use std::fmt;
 use std::sync::Arc;
 
+/// Моментна снимка на вътрешното състояние, върната от `reflect`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReflectionReport {
+    pub memories: usize,
+    pub entropy: f64,
+    pub manifolds: usize,
+}
+
+impl fmt::Display for ReflectionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "I exist. I have {} memories across {} manifolds. Entropy: {:.4}.",
+            self.memories, self.manifolds, self.entropy
+        )
+    }
+}
+
 pub struct AmnioticEngine {
     memory: Arc<VectorSpaceHeap>,
     oracle: Box<dyn NeuralOracle + Send + Sync>,
@@ -88,21 +108,44 @@ impl AmnioticEngine {
         let result = self.oracle.infer(goal, context);
 
         // 4. Consolidate new memory (Experience)
-        self.memory.allocate(
+        if let Err(e) = self.memory.allocate(
             format!("Executed: {} -> Result: {}", goal, result),
             goal_vector,
-        );
+        ) {
+            println!("[SPIRIT] Experience not consolidated: {}", e);
+        }
 
         result
     }
 
     // Step 3.5: Reflection Layer
-    pub fn reflect(&self) -> String {
+    pub fn reflect(&self) -> ReflectionReport {
         println!("[REFLECTION] Analyzing internal state...");
-        // Simple reflection: Count memories
-        format!(
-            "I exist. I have {} memories.",
-            self.memory.recall(&[0.0; 128], 1000).len()
-        )
+        ReflectionReport {
+            memories: self.memory.points.len(),
+            entropy: self.memory.get_global_entropy(),
+            manifolds: self.memory.manifolds.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::vsh::Manifold;
+
+    #[test]
+    fn test_reflect_reports_heap_state() {
+        let engine = AmnioticEngine::new("unused");
+        for i in 0..1500 {
+            engine.memory.allocate(format!("M{}", i), vec![1.0]).unwrap();
+        }
+        engine.memory.manifolds.insert("core".into(), Manifold::new("core", 1.0));
+
+        let report = engine.reflect();
+
+        assert_eq!(report.memories, 1500);
+        assert_eq!(report.manifolds, 1);
+        assert_eq!(report.entropy, 0.5);
     }
 }
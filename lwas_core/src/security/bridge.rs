@@ -12,6 +12,10 @@ impl SovereignBridge {
     pub fn trigger_autonomous_check() -> SovereignResult<String> {
         println!("⚡ JULES: Инициирам автономен одит на системата под OMNI_ACCESS...");
 
+        if crate::mode::is_simulation() {
+            return Ok("🧪 [SIMULATION]: cargo check --release skipped.".into());
+        }
+
         // JULES вече има правото да вика системни инструменти директно
         let output = Command::new("cargo")
             .arg("check")
@@ -26,3 +30,14 @@ impl SovereignBridge {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_autonomous_check_skips_cargo_in_simulation() {
+        let report = SovereignBridge::trigger_autonomous_check().unwrap();
+        assert!(report.contains("[SIMULATION]"), "{}", report);
+    }
+}
@@ -4,4 +4,5 @@
 pub mod bridge;
 pub mod guard;
 pub mod ledger;
+pub mod safe_path;
 pub mod sovereign_identity;
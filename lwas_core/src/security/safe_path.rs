@@ -0,0 +1,70 @@
+use crate::prelude::*;
+use std::path::{Path, PathBuf};
+
+/*
+ * Principle: Bounded Reach.
+ * Път, за който е доказано, че лежи вътре в работното пространство.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafePath(PathBuf);
+
+impl SafePath {
+    /// Канонизира `input` (относителните пътища се четат спрямо `root`) и отхвърля
+    /// всичко, което след разрешаване на `..` и symlink-ове излиза извън `root`.
+    pub fn resolve(root: &Path, input: &Path) -> SovereignResult<Self> {
+        let root = root
+            .canonicalize()
+            .map_err(|e| SovereignError::IoError(format!("WORKSPACE_ROOT: {}", e)))?;
+        let candidate = if input.is_absolute() {
+            input.to_path_buf()
+        } else {
+            root.join(input)
+        };
+        let resolved = candidate
+            .canonicalize()
+            .map_err(|e| SovereignError::IoError(format!("{:?}: {}", input, e)))?;
+
+        if !resolved.starts_with(&root) {
+            println!("[SECURITY ALERT] Path escape attempt: {:?}", input);
+            return Err(SovereignError::SecurityViolation);
+        }
+        Ok(Self(resolved))
+    }
+
+    pub fn as_path(&self) -> &Path {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> PathBuf {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn workspace() -> PathBuf {
+        let root = std::env::temp_dir().join(format!("lwas_safe_path_{}", Uuid::new_v4()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_in_workspace_path_is_allowed() {
+        let root = workspace();
+        let safe = SafePath::resolve(&root, Path::new("src")).unwrap();
+        assert_eq!(safe.as_path(), root.join("src").canonicalize().unwrap());
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_traversal_is_rejected() {
+        let root = workspace();
+        let escaped = SafePath::resolve(&root, Path::new("src/../../"));
+        assert_eq!(escaped, Err(SovereignError::SecurityViolation));
+        assert!(SafePath::resolve(&root, Path::new("/")).is_err());
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,34 @@
+// lwas_core/tests/genesis.rs
+// Пълният път: .soul -> AST -> байткод -> VM.
+
+use aeterna_node::vm::disasm::mnemonic;
+use aeterna_node::vm::interpreter::VirtualMachine;
+use lwas_core::omega::soul_compiler::SoulCompiler;
+use lwas_parser::parse_soul;
+
+const GENESIS: &str = include_str!("fixtures/genesis.soul");
+
+#[test]
+fn genesis_soul_runs_end_to_end() {
+    let ast = parse_soul(GENESIS).expect("genesis fixture must parse");
+    let bytecode = SoulCompiler::compile(ast).expect("genesis fixture must compile");
+
+    let mut vm = VirtualMachine::new(bytecode).with_trace();
+    vm.run().unwrap();
+
+    let executed: Vec<&str> = vm.executed().map(mnemonic).collect();
+    assert_eq!(
+        executed,
+        [
+            "DEFINE_MATTER",
+            "RESONATE_MEMBRANE",
+            "VERIFY_TIMELINE",
+            "ONTOLOGICAL_SHIFT",
+            "INVERT_ENTROPY",
+            "HALT",
+        ]
+    );
+    assert_eq!(vm.defined_matter(), ["lattice = diamond".to_string()]);
+    // Без ledger VERIFY_TIMELINE не намира събитието и избутва 0.
+    assert_eq!(vm.stack, vec![0]);
+}
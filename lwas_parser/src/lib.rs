@@ -1,2 +1,2 @@
 pub mod parser;
-pub use parser::{parse_soul, AstNode, EntrenchValue};
+pub use parser::{parse_soul, parse_soul_spanned, AstNode, EntrenchValue, ParseError, Spanned};
@@ -64,45 +64,90 @@ pub enum EntrenchValue {
     Number(f32),
 }
 
+/// Възел заедно с реда и колоната (от 1), където започва в изходния `.soul` файл.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub line: usize,
+    pub col: usize,
+}
+
 #[derive(Error, Debug)]
 pub enum ParseError {
     #[error("Parsing error: {0}")]
     Pest(#[from] pest::error::Error<Rule>),
+    #[error("Malformed statement at line {line}, column {col}: missing {missing}")]
+    Statement {
+        line: usize,
+        col: usize,
+        missing: &'static str,
+    },
+}
+
+impl ParseError {
+    /// Ред и колона (от 1) на мястото, където парсерът се е провалил.
+    pub fn line_col(&self) -> (usize, usize) {
+        match self {
+            ParseError::Pest(e) => match e.line_col {
+                pest::error::LineColLocation::Pos(pos) => pos,
+                pest::error::LineColLocation::Span(start, _) => start,
+            },
+            ParseError::Statement { line, col, .. } => (*line, *col),
+        }
+    }
 }
 
 pub fn parse_soul(input: &str) -> Result<Vec<AstNode>, ParseError> {
+    Ok(parse_soul_spanned(input)?
+        .into_iter()
+        .map(|spanned| spanned.node)
+        .collect())
+}
+
+/// Като `parse_soul`, но всеки top-level възел носи реда и колоната си.
+pub fn parse_soul_spanned(input: &str) -> Result<Vec<Spanned<AstNode>>, ParseError> {
     let mut pairs = LwasParser::parse(Rule::program, input)?;
     let program_pair = pairs.next().unwrap();
-    Ok(parse_statements(program_pair.into_inner()))
+    parse_statements(program_pair.into_inner())
 }
 
-fn parse_statements(pairs: pest::iterators::Pairs<Rule>) -> Vec<AstNode> {
+type Pairs<'i> = pest::iterators::Pairs<'i, Rule>;
+type Pair<'i> = pest::iterators::Pair<'i, Rule>;
+
+/// Следващата под-двойка на израз или `ParseError::Statement` с позицията на израза.
+fn expect<'i>(rules: &mut Pairs<'i>, (line, col): (usize, usize), missing: &'static str) -> Result<Pair<'i>, ParseError> {
+    rules.next().ok_or(ParseError::Statement { line, col, missing })
+}
+
+fn parse_statements(pairs: Pairs) -> Result<Vec<Spanned<AstNode>>, ParseError> {
     let mut ast = Vec::new();
     for pair in pairs {
         match pair.as_rule() {
             Rule::statement => {
-                let inner = pair.into_inner().next().unwrap();
+                let at = pair.as_span().start_pos().line_col();
+                let inner = expect(&mut pair.into_inner(), at, "statement body")?;
+                let mut push = |node: AstNode| {
+                    ast.push(Spanned { node, line: at.0, col: at.1 });
+                };
                 match inner.as_rule() {
                     Rule::immortal_decl => {
                         let mut inner_rules = inner.into_inner();
-                        let name = inner_rules.next().unwrap().as_str().to_string();
-                        let value = inner_rules
-                            .next()
-                            .unwrap()
+                        let name = expect(&mut inner_rules, at, "name")?.as_str().to_string();
+                        let value = expect(&mut inner_rules, at, "value")?
                             .as_str()
                             .trim_matches('"')
                             .to_string();
-                        ast.push(AstNode::Immortal { name, value });
+                        push(AstNode::Immortal { name, value });
                     }
                     Rule::body_block => {
                         let mut inner_rules = inner.into_inner();
-                        let name = inner_rules.next().unwrap().as_str().to_string();
-                        let content = inner_rules.next().unwrap().as_str().trim().to_string();
-                        ast.push(AstNode::Body { name, content });
+                        let name = expect(&mut inner_rules, at, "name")?.as_str().to_string();
+                        let content = expect(&mut inner_rules, at, "content")?.as_str().trim().to_string();
+                        push(AstNode::Body { name, content });
                     }
                     Rule::spirit_block => {
                         let mut inner_rules = inner.into_inner();
-                        let name = inner_rules.next().unwrap().as_str().to_string();
+                        let name = expect(&mut inner_rules, at, "name")?.as_str().to_string();
                         let mut goal = String::new();
                         for field in inner_rules {
                             if field.as_rule() == Rule::string_literal {
@@ -110,39 +155,42 @@ fn parse_statements(pairs: pest::iterators::Pairs<Rule>) -> Vec<AstNode> {
                                 break;
                             }
                         }
-                        ast.push(AstNode::Spirit { name, goal });
+                        push(AstNode::Spirit { name, goal });
                     }
                     Rule::manifold_block => {
                         let mut inner_rules = inner.into_inner();
-                        let name = inner_rules.next().unwrap().as_str().to_string();
-                        let body = parse_statements(inner_rules);
-                        ast.push(AstNode::Manifold { name, body });
+                        let name = expect(&mut inner_rules, at, "name")?.as_str().to_string();
+                        let body = parse_statements(inner_rules)?
+                            .into_iter()
+                            .map(|spanned| spanned.node)
+                            .collect();
+                        push(AstNode::Manifold { name, body });
                     }
                     Rule::resonate_stmt => {
                         let mut inner_rules = inner.into_inner();
-                        let target = inner_rules.next().unwrap().as_str().to_string();
+                        let target = expect(&mut inner_rules, at, "target")?.as_str().to_string();
                         let frequency = inner_rules
                             .next()
                             .map(|n| n.as_str().parse::<f64>().unwrap_or(1.0))
                             .unwrap_or(1.0);
-                        ast.push(AstNode::Resonate { target, frequency });
+                        push(AstNode::Resonate { target, frequency });
                     }
                     Rule::collapse_stmt => {
                         let mut inner_rules = inner.into_inner();
-                        let target = inner_rules.next().unwrap().as_str().to_string();
+                        let target = expect(&mut inner_rules, at, "target")?.as_str().to_string();
                         let entropy_threshold = inner_rules
                             .next()
                             .map(|n| n.as_str().parse::<f64>().unwrap_or(0.5))
                             .unwrap_or(0.5);
-                        ast.push(AstNode::Collapse {
+                        push(AstNode::Collapse {
                             target,
                             entropy_threshold,
                         });
                     }
                     Rule::entrench_stmt => {
                         let mut inner_rules = inner.into_inner();
-                        let key = inner_rules.next().unwrap().as_str().to_string();
-                        let val_pair = inner_rules.next().unwrap();
+                        let key = expect(&mut inner_rules, at, "key")?.as_str().to_string();
+                        let val_pair = expect(&mut inner_rules, at, "value")?;
                         let value = match val_pair.as_rule() {
                             Rule::vector => {
                                 let vec = val_pair
@@ -159,13 +207,11 @@ fn parse_statements(pairs: pest::iterators::Pairs<Rule>) -> Vec<AstNode> {
                             ),
                             _ => EntrenchValue::String("".into()),
                         };
-                        ast.push(AstNode::Entrench { key, value });
+                        push(AstNode::Entrench { key, value });
                     }
                     Rule::magnet_stmt => {
                         let mut inner_rules = inner.into_inner();
-                        let label = inner_rules
-                            .next()
-                            .unwrap()
+                        let label = expect(&mut inner_rules, at, "label")?
                             .as_str()
                             .trim_matches('"')
                             .to_string();
@@ -173,37 +219,35 @@ fn parse_statements(pairs: pest::iterators::Pairs<Rule>) -> Vec<AstNode> {
                             .next()
                             .map(|n| n.as_str().parse::<f64>().unwrap_or(1.0))
                             .unwrap_or(1.0);
-                        ast.push(AstNode::Magnet { label, power });
+                        push(AstNode::Magnet { label, power });
                     }
                     Rule::department_stmt => {
                         let mut inner_rules = inner.into_inner();
-                        let name = inner_rules.next().unwrap().as_str().to_string();
+                        let name = expect(&mut inner_rules, at, "name")?.as_str().to_string();
                         let priority = inner_rules
                             .next()
                             .map(|n| n.as_str().parse::<f64>().unwrap_or(1.0))
                             .unwrap_or(1.0);
-                        ast.push(AstNode::Department { name, priority });
+                        push(AstNode::Department { name, priority });
                     }
                     Rule::reflection_stmt => {
-                        ast.push(AstNode::Reflect);
+                        push(AstNode::Reflect);
                     }
                     Rule::axiom_stmt => {
                         let mut inner_rules = inner.into_inner();
-                        let name = inner_rules.next().unwrap().as_str().to_string();
-                        let expression = inner_rules
-                            .next()
-                            .unwrap()
+                        let name = expect(&mut inner_rules, at, "name")?.as_str().to_string();
+                        let expression = expect(&mut inner_rules, at, "expression")?
                             .as_str()
                             .trim_matches('"')
                             .to_string();
-                        ast.push(AstNode::Axiom { name, expression });
+                        push(AstNode::Axiom { name, expression });
                     }
                     Rule::causality_stmt => {
                         let mut inner_rules = inner.into_inner();
-                        let cause = inner_rules.next().unwrap().as_str().to_string();
-                        let effect = inner_rules.next().unwrap().as_str().to_string();
-                        let c_type = inner_rules.next().unwrap().as_str().to_string();
-                        ast.push(AstNode::Causality {
+                        let cause = expect(&mut inner_rules, at, "cause")?.as_str().to_string();
+                        let effect = expect(&mut inner_rules, at, "effect")?.as_str().to_string();
+                        let c_type = expect(&mut inner_rules, at, "causality type")?.as_str().to_string();
+                        push(AstNode::Causality {
                             cause,
                             effect,
                             c_type,
@@ -215,5 +259,20 @@ fn parse_statements(pairs: pest::iterators::Pairs<Rule>) -> Vec<AstNode> {
             _ => {}
         }
     }
-    ast
+    Ok(ast)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_statement_reports_line_two() {
+        let nodes = parse_soul_spanned("immortal Core = \"A\";\n  resonate Core 7.5;").unwrap();
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!((nodes[0].line, nodes[0].col), (1, 1));
+        assert_eq!((nodes[1].line, nodes[1].col), (2, 3));
+        assert!(matches!(nodes[1].node, AstNode::Resonate { .. }));
+    }
 }